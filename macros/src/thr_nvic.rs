@@ -66,6 +66,8 @@ enum Thread {
 struct ThreadSpec {
     attrs: Vec<Attribute>,
     vis: Visibility,
+    priority: Option<u8>,
+    guard: Option<ExprPath>,
     kind: ThreadKind,
     ident: Ident,
 }
@@ -73,6 +75,7 @@ struct ThreadSpec {
 enum ThreadKind {
     Inner,
     Outer(ExprPath),
+    Fast(ExprPath),
     Naked(ExprPath),
 }
 
@@ -218,9 +221,21 @@ impl Parse for Threads {
                 while !input3.is_empty() {
                     let attrs = input3.call(Attribute::parse_outer)?;
                     let vis = input3.parse()?;
+                    let priority = parse_priority(&input3)?;
+                    if parse_guard(&input3)?.is_some() {
+                        return Err(input3.error("`guard` is allowed only for interrupts"));
+                    }
                     let kind = input3.parse()?;
+                    check_link_section(&input3, &attrs, &kind)?;
                     let ident = input3.parse()?;
-                    threads.push(Thread::Exception(ThreadSpec { attrs, vis, kind, ident }));
+                    threads.push(Thread::Exception(ThreadSpec {
+                        attrs,
+                        vis,
+                        priority,
+                        guard: None,
+                        kind,
+                        ident,
+                    }));
                     if !input3.is_empty() {
                         input3.parse::<Token![;]>()?;
                     }
@@ -233,9 +248,22 @@ impl Parse for Threads {
                     let num = input3.parse::<LitInt>()?.base10_parse()?;
                     input3.parse::<Token![:]>()?;
                     let vis = input3.parse()?;
+                    let priority = parse_priority(&input3)?;
+                    let guard = parse_guard(&input3)?;
                     let kind = input3.parse()?;
+                    if guard.is_some() && matches!(kind, ThreadKind::Naked(_)) {
+                        return Err(input3.error("`guard` is not allowed for naked threads"));
+                    }
+                    check_link_section(&input3, &attrs, &kind)?;
                     let ident = input3.parse()?;
-                    threads.push(Thread::Interrupt(num, ThreadSpec { attrs, vis, kind, ident }));
+                    threads.push(Thread::Interrupt(num, ThreadSpec {
+                        attrs,
+                        vis,
+                        priority,
+                        guard,
+                        kind,
+                        ident,
+                    }));
                     if !input3.is_empty() {
                         input3.parse::<Token![;]>()?;
                     }
@@ -261,6 +289,13 @@ impl Parse for ThreadKind {
                 let path = input2.parse()?;
                 Ok(Self::Outer(path))
             }
+            Ok(ident) if ident == "fast" => {
+                input.parse::<Ident>()?;
+                let input2;
+                parenthesized!(input2 in input);
+                let path = input2.parse()?;
+                Ok(Self::Fast(path))
+            }
             Ok(ident) if ident == "naked" => {
                 input.parse::<Ident>()?;
                 let input2;
@@ -273,6 +308,45 @@ impl Parse for ThreadKind {
     }
 }
 
+fn parse_priority(input: ParseStream<'_>) -> Result<Option<u8>> {
+    match input.fork().parse::<Ident>() {
+        Ok(ident) if ident == "priority" => {
+            input.parse::<Ident>()?;
+            let input2;
+            parenthesized!(input2 in input);
+            Ok(Some(input2.parse::<LitInt>()?.base10_parse()?))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn parse_guard(input: ParseStream<'_>) -> Result<Option<ExprPath>> {
+    match input.fork().parse::<Ident>() {
+        Ok(ident) if ident == "guard" => {
+            input.parse::<Ident>()?;
+            let input2;
+            parenthesized!(input2 in input);
+            Ok(Some(input2.parse()?))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn check_link_section(
+    input: ParseStream<'_>,
+    attrs: &[Attribute],
+    kind: &ThreadKind,
+) -> Result<()> {
+    if matches!(kind, ThreadKind::Naked(_)) && attrs.iter().any(is_link_section) {
+        return Err(input.error("`link_section` is not allowed for naked threads"));
+    }
+    Ok(())
+}
+
+fn is_link_section(attr: &Attribute) -> bool {
+    attr.path.is_ident("link_section")
+}
+
 pub fn proc_macro(input: TokenStream) -> TokenStream {
     let Input { thr, local, index, vtable, init, sv, threads } = parse_macro_input!(input as Input);
     let Threads { threads } = threads;
@@ -302,7 +376,7 @@ fn partition_threads(threads: Vec<Thread>) -> (Vec<Thread>, Vec<Thread>) {
         Thread::Exception(spec) | Thread::Interrupt(_, spec) => {
             let ThreadSpec { kind, .. } = spec;
             match kind {
-                ThreadKind::Inner | ThreadKind::Outer(_) => true,
+                ThreadKind::Inner | ThreadKind::Outer(_) | ThreadKind::Fast(_) => true,
                 ThreadKind::Naked(_) => false,
             }
         }
@@ -332,21 +406,26 @@ fn def_vtable(
     {
         match thread {
             Thread::Exception(spec) | Thread::Interrupt(_, spec) => {
-                let ThreadSpec { kind, ident, .. } = spec;
+                let ThreadSpec { attrs, guard, kind, ident, .. } = spec;
                 let field_ident = format_ident!("{}", ident);
+                let link_section =
+                    attrs.iter().filter(|attr| is_link_section(attr)).collect::<Vec<_>>();
+                let guard_check = guard.as_ref().map(|path| {
+                    let struct_ident = format_ident!("{}", ident.to_string().to_pascal_case());
+                    quote! {
+                        if !unsafe { #path.check::<#struct_ident>() } {
+                            return;
+                        }
+                    }
+                });
                 match kind {
                     ThreadKind::Inner => {
                         let ident = format_ident!("thr_handler_{}", idx.unwrap());
-                        resume_tokens.get_or_insert_with(|| {
-                            quote! {
-                                #[inline(never)]
-                                unsafe fn #resume(thr: &#thr_ident) {
-                                    unsafe { ::drone_core::thr::Thread::resume(thr) };
-                                }
-                            }
-                        });
+                        resume_tokens.get_or_insert_with(|| def_resume(thr_ident, &resume));
                         tokens.push(quote! {
+                            #(#link_section)*
                             unsafe extern "C" fn #ident() {
+                                #guard_check
                                 unsafe { <#thr_ident as ::drone_core::thr::Thread>::call(#idx, #resume) };
                             }
                         });
@@ -357,7 +436,9 @@ fn def_vtable(
                     ThreadKind::Outer(path) => {
                         let ident = format_ident!("thr_handler_{}_outer", idx.unwrap());
                         tokens.push(quote! {
+                            #(#link_section)*
                             unsafe extern "C" fn #ident() {
+                                #guard_check
                                 unsafe { <#thr_ident as ::drone_core::thr::Thread>::call(#idx, #path) };
                             }
                         });
@@ -365,6 +446,22 @@ fn def_vtable(
                             #field_ident: ::core::option::Option::Some(#ident)
                         });
                     }
+                    ThreadKind::Fast(path) => {
+                        let ident = format_ident!("thr_handler_{}_fast", idx.unwrap());
+                        resume_tokens.get_or_insert_with(|| def_resume(thr_ident, &resume));
+                        tokens.push(quote! {
+                            #(#link_section)*
+                            unsafe extern "C" fn #ident() {
+                                #guard_check
+                                if #path() {
+                                    unsafe { <#thr_ident as ::drone_core::thr::Thread>::call(#idx, #resume) };
+                                }
+                            }
+                        });
+                        vtable_ctor_tokens.push(quote! {
+                            #field_ident: ::core::option::Option::Some(#ident)
+                        });
+                    }
                     ThreadKind::Naked(path) => {
                         vtable_ctor_tokens.push(quote! {
                             #field_ident: ::core::option::Option::Some(#path)
@@ -386,6 +483,7 @@ fn def_vtable(
             }
         }
     }
+    let vectors_tokens = def_vectors(threads, naked_threads);
     let vtable_tokens = vtable_tokens
         .into_iter()
         .enumerate()
@@ -450,6 +548,11 @@ fn def_vtable(
                     }
                 }
             }
+
+            /// Vectors configured in the table, ordered by exception number.
+            pub const VECTORS: &'static [::drone_cortexm::thr::Vector] = &[
+                #(#vectors_tokens),*
+            ];
         }
 
         #resume_tokens
@@ -457,6 +560,79 @@ fn def_vtable(
     }
 }
 
+fn def_resume(thr_ident: &Ident, resume: &Ident) -> TokenStream2 {
+    quote! {
+        #[inline(never)]
+        unsafe fn #resume(thr: &#thr_ident) {
+            unsafe { ::drone_core::thr::Thread::resume(thr) };
+        }
+    }
+}
+
+fn def_vectors(threads: &[Thread], naked_threads: &[Thread]) -> Vec<TokenStream2> {
+    let mut vectors = threads
+        .iter()
+        .chain(naked_threads)
+        .filter_map(|thread| {
+            let (exc_num, spec) = match thread {
+                Thread::Exception(spec) => (exception_number(&spec.ident)?, spec),
+                Thread::Interrupt(num, spec) => (num + 16, spec),
+            };
+            let ThreadSpec { priority, guard, kind, ident, .. } = spec;
+            let thread = ident.to_string();
+            let (kind, handler) = match kind {
+                ThreadKind::Inner => (quote!(Inner), thread.clone()),
+                ThreadKind::Outer(path) => (quote!(Outer), path_string(path)),
+                ThreadKind::Fast(path) => (quote!(Fast), path_string(path)),
+                ThreadKind::Naked(path) => (quote!(Naked), path_string(path)),
+            };
+            let priority = match priority {
+                Some(priority) => quote!(::core::option::Option::Some(#priority)),
+                None => quote!(::core::option::Option::None),
+            };
+            let guard = match guard {
+                Some(path) => {
+                    let path = path_string(path);
+                    quote!(::core::option::Option::Some(#path))
+                }
+                None => quote!(::core::option::Option::None),
+            };
+            Some((exc_num, quote! {
+                ::drone_cortexm::thr::Vector {
+                    exc_num: #exc_num,
+                    thread: #thread,
+                    kind: ::drone_cortexm::thr::VectorKind::#kind,
+                    handler: #handler,
+                    priority: #priority,
+                    guard: #guard,
+                }
+            }))
+        })
+        .collect::<Vec<_>>();
+    vectors.sort_by_key(|(exc_num, _)| *exc_num);
+    vectors.into_iter().map(|(_, tokens)| tokens).collect()
+}
+
+fn exception_number(ident: &Ident) -> Option<u16> {
+    Some(match ident.to_string().as_str() {
+        "nmi" => 2,
+        "hard_fault" => 3,
+        "mem_manage" => 4,
+        "bus_fault" => 5,
+        "usage_fault" => 6,
+        "secure_fault" => 7,
+        "sv_call" => 11,
+        "debug" => 12,
+        "pend_sv" => 14,
+        "sys_tick" => 15,
+        _ => return None,
+    })
+}
+
+fn path_string(path: &ExprPath) -> String {
+    quote!(#path).to_string().replace(' ', "")
+}
+
 fn def_init(index: &Index, init: &Init) -> TokenStream2 {
     let Init { attrs: init_attrs, vis: init_vis, ident: init_ident } = init;
     let Index { ident: index_ident, .. } = index;
@@ -491,6 +667,7 @@ fn def_thr_pool(thr: &Thr, local: &Local, index: &Index, threads: &[Thread]) ->
         match thread {
             Thread::Exception(spec) | Thread::Interrupt(_, spec) => {
                 let ThreadSpec { attrs, vis, ident, .. } = spec;
+                let attrs = attrs.iter().filter(|attr| !is_link_section(attr)).collect::<Vec<_>>();
                 threads_tokens.push(quote! {
                     #(#attrs)* #vis #ident
                 });
@@ -521,7 +698,7 @@ fn def_thr_token(sv: &Option<Sv>, thread: &Thread) -> Vec<TokenStream2> {
         Thread::Exception(spec) | Thread::Interrupt(_, spec) => {
             let ThreadSpec { kind, ident, .. } = spec;
             match kind {
-                ThreadKind::Inner | ThreadKind::Outer(_) => {
+                ThreadKind::Inner | ThreadKind::Outer(_) | ThreadKind::Fast(_) => {
                     let struct_ident = format_ident!("{}", ident.to_string().to_pascal_case());
                     if let Some(Sv { path: sv_path }) = sv {
                         tokens.push(quote! {
@@ -0,0 +1,99 @@
+//! Build information embedded into the firmware.
+//!
+//! The [`embed!`] macro places a [`BuildInfo`] record into `.rodata.buildinfo`
+//! section of the application binary, so the deployed firmware can always
+//! report what it is, and host tools can locate the record in the ELF file.
+//!
+//! The package name and version are taken from Cargo. The git hash and the
+//! build timestamp are taken from `DRONE_GIT_HASH` and `DRONE_BUILD_TIMESTAMP`
+//! environment variables at compile-time, which can be set by the application
+//! build script:
+//!
+//! ```no_run
+//! // build.rs
+//! use std::process::Command;
+//!
+//! fn main() {
+//!     let output = Command::new("git").args(&["rev-parse", "--short", "HEAD"]).output().unwrap();
+//!     let hash = String::from_utf8(output.stdout).unwrap();
+//!     println!("cargo:rustc-env=DRONE_GIT_HASH={}", hash.trim());
+//! }
+//! ```
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use drone_cortexm::buildinfo;
+//!
+//! buildinfo::embed!();
+//!
+//! # fn main() {
+//! buildinfo::print_banner();
+//! # }
+//! ```
+
+/// Build information record.
+#[derive(Clone, Copy, Debug)]
+pub struct BuildInfo {
+    /// The application package name.
+    pub name: &'static str,
+    /// The application package version.
+    pub version: &'static str,
+    /// The git commit hash.
+    pub git_hash: Option<&'static str>,
+    /// The build timestamp.
+    pub timestamp: Option<&'static str>,
+}
+
+extern "Rust" {
+    static DRONE_BUILDINFO: BuildInfo;
+}
+
+/// Returns the build information record of the application.
+///
+/// The record should be defined with [`embed!`] macro.
+#[inline]
+pub fn get() -> &'static BuildInfo {
+    unsafe { &DRONE_BUILDINFO }
+}
+
+/// Prints the build information to the standard output.
+///
+/// The banner is not printed automatically.
+/// [`swo::set_log!`](crate::swo::set_log) only defines the log output
+/// functions, and the SWO output is configured by the debug probe, so this
+/// crate has no point where the console gets initialized. Call this function
+/// at the beginning of the application instead.
+pub fn print_banner() {
+    let BuildInfo { name, version, git_hash, timestamp } = get();
+    print!("{} {}", name, version);
+    if let Some(git_hash) = git_hash {
+        print!(" ({})", git_hash);
+    }
+    if let Some(timestamp) = timestamp {
+        print!(" built {}", timestamp);
+    }
+    println!();
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! buildinfo_embed {
+    () => {
+        #[no_mangle]
+        #[used]
+        #[link_section = ".rodata.buildinfo"]
+        static DRONE_BUILDINFO: $crate::buildinfo::BuildInfo = $crate::buildinfo::BuildInfo {
+            name: ::core::env!("CARGO_PKG_NAME"),
+            version: ::core::env!("CARGO_PKG_VERSION"),
+            git_hash: ::core::option_env!("DRONE_GIT_HASH"),
+            timestamp: ::core::option_env!("DRONE_BUILD_TIMESTAMP"),
+        };
+    };
+}
+
+/// Embeds the build information record into the application.
+///
+/// See [the module level documentation](self) for details.
+#[doc(inline)]
+pub use crate::buildinfo_embed as embed;
@@ -0,0 +1,124 @@
+//! Deadline monitoring for futures.
+//!
+//! A [`Deadline`] is a static per-site budget of processor cycles. Futures
+//! wrapped with [`Deadline::monitor`] are measured from the first poll to the
+//! completion. When a future misses the deadline, the miss is counted, reported
+//! to the standard error log stream, and [`debug::break_on_error`] is called,
+//! so the processor halts in the debugger if enabled.
+//!
+//! The monitor doesn't interrupt the future; combine it with a timer to
+//! implement timeouts.
+//!
+//! The time is measured with the DWT cycle counter, which is stopped from reset
+//! and is usually started only by a debugger. The application should start it
+//! with [`processor::enable_cycle_counter`] during initialization. While the
+//! counter is stopped, completed futures are not recorded at all, rather than
+//! recorded as taking zero cycles. Budgets above `u32::MAX` cycles are not
+//! supported.
+//!
+//! [`swo::sync`](crate::swo::sync), which is also called by
+//! [`swo::update_prescaler`](crate::swo::update_prescaler), overwrites the
+//! cycle counter. A future, which is being monitored during that call, gets a
+//! bogus completion time, so these functions should be called before any
+//! monitored future is started.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use drone_cortexm::deadline::Deadline;
+//!
+//! // Allow at most 72_000 cycles for the control loop step.
+//! static CONTROL_STEP: Deadline = Deadline::new(72_000);
+//!
+//! async fn control_step() {}
+//!
+//! async fn control_loop() {
+//!     loop {
+//!         CONTROL_STEP.monitor(control_step()).await;
+//!     }
+//! }
+//! ```
+
+use crate::{debug, processor};
+use core::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicU32, Ordering},
+    task::{Context, Poll},
+};
+
+/// A budget of processor cycles with miss statistics.
+pub struct Deadline {
+    budget: u32,
+    misses: AtomicU32,
+    worst: AtomicU32,
+}
+
+/// Future for [`Deadline::monitor`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Monitor<F: Future> {
+    deadline: &'static Deadline,
+    start: Option<u32>,
+    future: F,
+}
+
+impl Deadline {
+    /// Creates a new deadline of `budget` cycles.
+    pub const fn new(budget: u32) -> Self {
+        Self { budget, misses: AtomicU32::new(0), worst: AtomicU32::new(0) }
+    }
+
+    /// Wraps `future` to check whether it completes within the budget.
+    #[inline]
+    pub fn monitor<F: Future>(&'static self, future: F) -> Monitor<F> {
+        Monitor { deadline: self, start: None, future }
+    }
+
+    /// Returns the budget in cycles.
+    #[inline]
+    pub fn budget(&self) -> u32 {
+        self.budget
+    }
+
+    /// Returns the number of missed deadlines.
+    #[inline]
+    pub fn misses(&self) -> u32 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the worst observed completion time in cycles.
+    #[inline]
+    pub fn worst(&self) -> u32 {
+        self.worst.load(Ordering::Relaxed)
+    }
+
+    /// Records a completion, which took `elapsed` cycles. Returns `false` if
+    /// the deadline is missed, in which case the miss is counted and reported.
+    ///
+    /// This is the bookkeeping part of [`Monitor`], which can be used to
+    /// measure a synchronous piece of code.
+    pub fn record(&self, elapsed: u32) -> bool {
+        self.worst.fetch_max(elapsed, Ordering::Relaxed);
+        if elapsed > self.budget {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            eprintln!("Deadline missed: {} cycles of {} budget", elapsed, self.budget);
+            debug::break_on_error();
+            return false;
+        }
+        true
+    }
+}
+
+impl<F: Future> Future for Monitor<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let Self { deadline, start, future } = unsafe { self.get_unchecked_mut() };
+        let start = *start.get_or_insert_with(processor::cycle_count);
+        let output = unsafe { Pin::new_unchecked(future) }.poll(cx);
+        if output.is_ready() && processor::is_cycle_counter_enabled() {
+            deadline.record(processor::cycle_count().wrapping_sub(start));
+        }
+        output
+    }
+}
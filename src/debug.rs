@@ -0,0 +1,122 @@
+//! Debugger interaction.
+//!
+//! [`is_attached`] tells whether a debugger is attached to the processor, so
+//! the firmware can adjust its behavior for a development session. E.g. a
+//! device-specific watchdog driver can freeze the watchdog counter while the
+//! core is halted.
+//!
+//! # Halting instead of resetting
+//!
+//! Panics and other fatal errors reset the system by default. When enabled
+//! with [`set_halt_on_reset`], the processor halts at a breakpoint instead, if a
+//! debugger is attached, so the failed state can be inspected.
+//!
+//! # Breaking on errors
+//!
+//! During development it is often handy to stop right at the place where an
+//! error has been produced, rather than to track down a propagated `Err` value.
+//! When enabled with [`set_break_on_error`], [`break_on_error`] executes a
+//! `BKPT` instruction if a debugger is attached. Without a debugger, or when
+//! not enabled, errors remain plain `Err` values.
+//!
+//! ```no_run
+//! use drone_cortexm::debug::{self, BreakOnErr};
+//!
+//! fn transfer() -> Result<(), ()> {
+//!     Err(())
+//! }
+//!
+//! debug::set_break_on_error(true);
+//! // Halts here if a debugger is attached.
+//! let _ = transfer().break_on_err();
+//! ```
+
+#![cfg_attr(feature = "std", allow(unreachable_code))]
+
+use core::{
+    ptr::read_volatile,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+const DHCSR: usize = 0xE000_EDF0;
+const DHCSR_C_DEBUGEN: u32 = 1 << 0;
+
+static BREAK_ON_ERROR: AtomicBool = AtomicBool::new(false);
+static HALT_ON_RESET: AtomicBool = AtomicBool::new(false);
+
+/// An extension trait for `Result` to break into the debugger on errors.
+pub trait BreakOnErr {
+    /// Calls [`break_on_error`] if `self` is `Err`, and returns `self`
+    /// unchanged.
+    fn break_on_err(self) -> Self;
+}
+
+impl<T, E> BreakOnErr for Result<T, E> {
+    #[inline]
+    fn break_on_err(self) -> Self {
+        if self.is_err() {
+            break_on_error();
+        }
+        self
+    }
+}
+
+/// Returns `true` if a debugger is attached to the processor.
+///
+/// Reads the `C_DEBUGEN` bit of the Debug Halting Control and Status Register,
+/// which is set by the debugger when it enables halting debug.
+#[inline]
+pub fn is_attached() -> bool {
+    #[cfg(feature = "std")]
+    return unimplemented!();
+    unsafe { read_volatile(DHCSR as *const u32) & DHCSR_C_DEBUGEN != 0 }
+}
+
+/// Enables or disables breaking into the debugger by [`break_on_error`].
+/// Disabled by default.
+#[inline]
+pub fn set_break_on_error(enabled: bool) {
+    BREAK_ON_ERROR.store(enabled, Ordering::Relaxed);
+}
+
+/// Executes a `BKPT` instruction if breaking on errors is enabled with
+/// [`set_break_on_error`] and a debugger is attached. Otherwise does nothing.
+///
+/// Drivers call this function from their error paths.
+#[inline]
+pub fn break_on_error() {
+    if BREAK_ON_ERROR.load(Ordering::Relaxed) && is_attached() {
+        breakpoint();
+    }
+}
+
+/// Enables or disables halting at a breakpoint instead of the system reset on
+/// panics and other fatal errors, when a debugger is attached. Disabled by
+/// default.
+///
+/// This doesn't affect explicit calls to
+/// [`processor::self_reset`](crate::processor::self_reset).
+#[inline]
+pub fn set_halt_on_reset(enabled: bool) {
+    HALT_ON_RESET.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns `true` if a fatal error should halt the processor instead of
+/// resetting. See [`set_halt_on_reset`].
+pub(crate) fn is_halt_on_reset() -> bool {
+    HALT_ON_RESET.load(Ordering::Relaxed) && is_attached()
+}
+
+/// Executes a `BKPT` instruction.
+///
+/// Without an attached debugger, the instruction escalates to a HardFault. See
+/// [`is_attached`].
+#[inline]
+pub fn breakpoint() {
+    #[cfg(feature = "std")]
+    return unimplemented!();
+    #[cfg(not(feature = "std"))]
+    unsafe {
+        asm!("bkpt", options(nomem, nostack, preserves_flags));
+    }
+}
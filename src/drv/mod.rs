@@ -8,3 +8,15 @@
 
 pub mod sys_tick;
 pub mod timer;
+#[cfg(all(
+    debug_assertions,
+    not(any(
+        cortexm_core = "cortexm33_r0p2",
+        cortexm_core = "cortexm33_r0p3",
+        cortexm_core = "cortexm33_r0p4",
+        cortexm_core = "cortexm33f_r0p2",
+        cortexm_core = "cortexm33f_r0p3",
+        cortexm_core = "cortexm33f_r0p4",
+    ))
+))]
+pub mod watchpoint;
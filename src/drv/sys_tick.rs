@@ -1,6 +1,7 @@
 //! SysTick timer.
 
 use crate::{
+    debug,
     drv::timer::{Timer, TimerInterval, TimerOverflow, TimerSleep, TimerStop},
     fib,
     fib::Fiber,
@@ -9,6 +10,7 @@ use crate::{
         reg::{scb, stk},
     },
     reg::{field::WWRegFieldBit, prelude::*},
+    system::Park,
     thr::prelude::*,
 };
 use core::{num::NonZeroUsize, pin::Pin, ptr::write_volatile};
@@ -58,7 +60,13 @@ impl<I: ThrToken> Timer for SysTick<I> {
         duration: u32,
     ) -> TimerInterval<'_, Self, Result<NonZeroUsize, TimerOverflow>> {
         self.interval_stream(duration, |int, ctrl| {
-            Box::pin(int.add_pulse_try_stream(|| Err(TimerOverflow), Self::interval_fib(ctrl)))
+            Box::pin(int.add_pulse_try_stream(
+                || {
+                    debug::break_on_error();
+                    Err(TimerOverflow)
+                },
+                Self::interval_fib(ctrl),
+            ))
         })
     }
 
@@ -76,6 +84,12 @@ impl<I: ThrToken> TimerStop for SysTick<I> {
     }
 }
 
+impl<I: ThrToken> Park for SysTick<I> {
+    fn park(&mut self) {
+        self.stop();
+    }
+}
+
 impl<I: ThrToken> SysTick<I> {
     /// Creates a new driver from the peripheral.
     #[inline]
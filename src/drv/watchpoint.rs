@@ -0,0 +1,202 @@
+//! Data watchpoints.
+//!
+//! This module turns DWT (Data Watchpoint and Trace) comparators into an
+//! on-target data race detector. A [`Watchpoint`] guards a memory location,
+//! usually a static variable, and makes every write to it generate a
+//! DebugMonitor exception. The [`debug_monitor_handler`] then checks whether
+//! the write was made by the registered owner thread, and reports the program
+//! counter of the offending write otherwise.
+//!
+//! The DebugMonitor exception should have a higher priority than any thread
+//! that can touch the guarded variable. Note that the DWT reports data
+//! watchpoints asynchronously, so the reported program counter may point a few
+//! instructions past the offending write.
+//!
+//! The DebugMonitor exception is not generated while the halting debug is
+//! enabled by a debugger. In this case a watchpoint hit halts the processor
+//! instead.
+//!
+//! The module is available only in debug builds (with `debug_assertions`
+//! enabled), so the detector can't be left in a release firmware by mistake.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # #![feature(const_fn_fn_ptr_basics)]
+//! # #![feature(proc_macro_hygiene)]
+//! # use drone_core::token::Token;
+//! use core::sync::atomic::{AtomicU32, Ordering};
+//! use drone_cortexm::{drv::watchpoint::Watchpoint, thr};
+//!
+//! thr::nvic! {
+//!     thread => pub Thr {};
+//!     local => pub ThrLocal {};
+//!     index => pub Thrs;
+//!     vtable => pub Vtable;
+//!     init => pub ThrsInit;
+//!     threads => {
+//!         exceptions => {
+//!             pub naked(drone_cortexm::drv::watchpoint::debug_monitor_handler) debug;
+//!         };
+//!         interrupts => {
+//!             10: pub exti4;
+//!         };
+//!     };
+//! }
+//!
+//! static SHARED: AtomicU32 = AtomicU32::new(0);
+//!
+//! # fn main() {
+//! #     let thr = unsafe { Thrs::take() };
+//! // Report all writes to `SHARED` made outside of the `exti4` thread.
+//! let watchpoint = Watchpoint::new(0, &SHARED, thr.exti4);
+//! SHARED.store(1, Ordering::Relaxed);
+//! drop(watchpoint);
+//! # }
+//! ```
+
+#![cfg_attr(feature = "std", allow(dead_code, unreachable_code, unused_variables))]
+
+use crate::{
+    map::reg::{dwt, scb},
+    reg::prelude::*,
+    thr::IntToken,
+};
+use core::{
+    mem::size_of,
+    ptr::{read_volatile, write_volatile},
+    sync::atomic::{AtomicU16, Ordering},
+};
+use drone_core::token::Token;
+
+/// Maximum number of DWT comparators.
+pub const COMPARATORS_MAX: u8 = 4;
+
+const DWT_COMP: usize = 0xE000_1020;
+const DWT_MASK: usize = 0xE000_1024;
+const DWT_FUNCTION: usize = 0xE000_1028;
+const DWT_STRIDE: usize = 0x10;
+
+const FUNCTION_WRITE: u32 = 0b0110;
+const FUNCTION_MATCHED: u32 = 1 << 24;
+
+const FREE: u16 = u16::MAX;
+
+static OWNERS: [AtomicU16; COMPARATORS_MAX as usize] =
+    [AtomicU16::new(FREE), AtomicU16::new(FREE), AtomicU16::new(FREE), AtomicU16::new(FREE)];
+
+/// DWT comparator guarding a memory location from foreign writes.
+///
+/// The comparator is released on drop.
+pub struct Watchpoint {
+    comp: u8,
+}
+
+impl Watchpoint {
+    /// Starts watching for writes to `var` made outside of the `owner` thread.
+    ///
+    /// # Panics
+    ///
+    /// * If `comp` is not implemented by the DWT unit.
+    /// * If `comp` is already in use by another [`Watchpoint`].
+    /// * If the address of `var` is not aligned to its size rounded up to a
+    ///   power of two.
+    pub fn new<T, O: IntToken>(comp: u8, var: &'static T, _owner: O) -> Self {
+        assert!(comp < comparators(), "DWT comparator is not implemented");
+        let address = var as *const T as usize;
+        let size = size_of::<T>().max(1).next_power_of_two();
+        assert!(address & (size - 1) == 0, "Watched variable is misaligned");
+        let claimed = OWNERS[usize::from(comp)]
+            .compare_exchange(FREE, O::INT_NUM + 16, Ordering::AcqRel, Ordering::Relaxed)
+            .is_ok();
+        assert!(claimed, "DWT comparator is already in use");
+        monitor_enable();
+        unsafe {
+            write_volatile(reg_ptr(DWT_COMP, comp), address as u32);
+            write_volatile(reg_ptr(DWT_MASK, comp), size.trailing_zeros());
+            write_volatile(reg_ptr(DWT_FUNCTION, comp), FUNCTION_WRITE);
+        }
+        Self { comp }
+    }
+
+    /// Returns the number of the DWT comparator.
+    #[inline]
+    pub fn comp(&self) -> u8 {
+        self.comp
+    }
+}
+
+impl Drop for Watchpoint {
+    fn drop(&mut self) {
+        unsafe { write_volatile(reg_ptr(DWT_FUNCTION, self.comp), 0) };
+        OWNERS[usize::from(self.comp)].store(FREE, Ordering::Release);
+    }
+}
+
+/// Returns the number of DWT comparators available for watchpoints.
+#[inline]
+pub fn comparators() -> u8 {
+    #[cfg(feature = "std")]
+    return unimplemented!();
+    let ctrl = unsafe { dwt::Ctrl::<Urt>::take() };
+    (ctrl.load().numcomp() as u8).min(COMPARATORS_MAX)
+}
+
+/// DebugMonitor exception handler, which reports foreign writes caught by
+/// [`Watchpoint`]s.
+///
+/// # Safety
+///
+/// This function should be called only by NVIC as part of the vector table.
+#[cfg_attr(not(feature = "std"), naked)]
+pub unsafe extern "C" fn debug_monitor_handler() {
+    #[cfg(feature = "std")]
+    return unimplemented!();
+    #[cfg(not(feature = "std"))]
+    unsafe {
+        asm!(
+            "tst lr, #4",
+            "ite eq",
+            "mrseq r0, msp",
+            "mrsne r0, psp",
+            "b {}",
+            sym debug_monitor,
+            options(noreturn),
+        );
+    }
+}
+
+unsafe extern "C" fn debug_monitor(frame: *const u32) {
+    let mut dfsr = unsafe { scb::Dfsr::<Urt>::take() };
+    if !dfsr.load().dwttrap() {
+        return;
+    }
+    dfsr.store(|r| r.set_dwttrap());
+    let pc = unsafe { *frame.add(6) };
+    let exception = unsafe { *frame.add(7) } as u16 & 0x1FF;
+    for comp in 0..comparators() {
+        // Reading the function register clears the matched flag.
+        let function = unsafe { read_volatile(reg_ptr(DWT_FUNCTION, comp)) };
+        if function & FUNCTION_MATCHED == 0 {
+            continue;
+        }
+        let owner = OWNERS[usize::from(comp)].load(Ordering::Acquire);
+        if owner != FREE && owner != exception {
+            eprintln!(
+                "Watchpoint #{}: write from exception #{} (owner #{}) at PC={:#010X}",
+                comp, exception, owner, pc
+            );
+        }
+    }
+}
+
+fn monitor_enable() {
+    #[cfg(feature = "std")]
+    return unimplemented!();
+    let demcr = unsafe { scb::Demcr::<Srt>::take() };
+    demcr.modify(|r| r.set_trcena().set_mon_en());
+}
+
+fn reg_ptr(base: usize, comp: u8) -> *mut u32 {
+    (base + usize::from(comp) * DWT_STRIDE) as *mut u32
+}
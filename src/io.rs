@@ -0,0 +1,270 @@
+//! Formatted output to arbitrary sinks.
+//!
+//! A [`Sink`] is a destination for bytes: an ITM stimulus port, a fixed RAM
+//! buffer, or a device-specific transport like USART or RTT. [`FmtWriter`]
+//! adapts any sink to [`core::fmt::Write`], and [`write_to!`] and
+//! [`writeln_to!`] macros format directly into a sink.
+//!
+//! # Examples
+//!
+//! ```
+//! use drone_cortexm::io::{self, Buffer};
+//!
+//! let mut storage = [0; 16];
+//! let mut buf = Buffer::new(&mut storage);
+//! io::writeln_to!(buf, "x = {}", 42);
+//! assert_eq!(buf.as_bytes(), b"x = 42\n");
+//! ```
+//!
+//! # Redirection
+//!
+//! The standard output and error streams used by `print!`, `eprintln!` and
+//! similar macros can be redirected at run-time with [`set_stdout`] and
+//! [`set_stderr`]. The redirection takes effect when SWO is set as the logger
+//! with [`swo::set_log!`](crate::swo::set_log). Streams which aren't redirected
+//! continue to go to the ITM.
+//!
+//! ```
+//! use drone_cortexm::io;
+//!
+//! fn usart_write(bytes: &[u8]) {
+//!     // Transmit `bytes` over USART.
+//! }
+//!
+//! io::set_stdout(Some(usart_write));
+//! io::set_stderr(Some(usart_write));
+//! ```
+
+use crate::swo;
+use core::{
+    fmt, mem,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A sink function for [`set_stdout`] and [`set_stderr`].
+pub type SinkFn = fn(&[u8]);
+
+// Port numbers of the standard streams used by `drone_core::log`.
+const STDOUT_PORT: u8 = 0;
+const STDERR_PORT: u8 = 1;
+
+static REDIRECTS: [AtomicUsize; 2] = [AtomicUsize::new(0), AtomicUsize::new(0)];
+
+/// A byte sink for formatted output.
+pub trait Sink {
+    /// Writes all of `bytes` to the sink.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the sink can't accept all of `bytes`.
+    fn write_all(&mut self, bytes: &[u8]) -> fmt::Result;
+}
+
+/// An adapter implementing [`core::fmt::Write`] for a [`Sink`].
+pub struct FmtWriter<S: Sink>(S);
+
+/// A [`Sink`] backed by a fixed RAM buffer.
+pub struct Buffer<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<S: Sink> FmtWriter<S> {
+    /// Creates a new adapter for `sink`.
+    #[inline]
+    pub fn new(sink: S) -> Self {
+        Self(sink)
+    }
+
+    /// Returns a reference to the underlying sink.
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the underlying sink.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.0
+    }
+
+    /// Returns the underlying sink.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S: Sink> fmt::Write for FmtWriter<S> {
+    #[inline]
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        self.0.write_all(string.as_bytes())
+    }
+}
+
+impl<'a> Buffer<'a> {
+    /// Creates a new empty sink backed by `buf`.
+    #[inline]
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// Returns the written bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Returns the number of written bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing was written.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the buffer has no space left.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == self.buf.len()
+    }
+
+    /// Discards the written bytes.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl Sink for Buffer<'_> {
+    /// Writes `bytes` into the buffer, truncating them to the space left.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `bytes` have been truncated.
+    fn write_all(&mut self, bytes: &[u8]) -> fmt::Result {
+        let count = bytes.len().min(self.buf.len() - self.len);
+        self.buf[self.len..self.len + count].copy_from_slice(&bytes[..count]);
+        self.len += count;
+        if count == bytes.len() { Ok(()) } else { Err(fmt::Error) }
+    }
+}
+
+impl Sink for swo::Port {
+    #[inline]
+    fn write_all(&mut self, bytes: &[u8]) -> fmt::Result {
+        self.write_bytes(bytes);
+        Ok(())
+    }
+}
+
+impl<S: Sink + ?Sized> Sink for &mut S {
+    #[inline]
+    fn write_all(&mut self, bytes: &[u8]) -> fmt::Result {
+        (**self).write_all(bytes)
+    }
+}
+
+/// Redirects the standard output stream to `sink`. `None` restores the
+/// default output.
+#[inline]
+pub fn set_stdout(sink: Option<SinkFn>) {
+    set_redirect(STDOUT_PORT, sink);
+}
+
+/// Redirects the standard error stream to `sink`. `None` restores the default
+/// output.
+#[inline]
+pub fn set_stderr(sink: Option<SinkFn>) {
+    set_redirect(STDERR_PORT, sink);
+}
+
+/// Returns `true` if the log port `port` is redirected.
+#[doc(hidden)]
+#[inline]
+pub fn is_redirected(port: u8) -> bool {
+    REDIRECTS.get(usize::from(port)).map_or(false, |sink| sink.load(Ordering::Acquire) != 0)
+}
+
+/// Writes `bytes` to the redirection sink of the log port `port`. Returns
+/// `false` if the port is not redirected.
+#[doc(hidden)]
+#[inline]
+pub fn redirect(port: u8, bytes: &[u8]) -> bool {
+    let sink = match REDIRECTS.get(usize::from(port)) {
+        Some(sink) => sink.load(Ordering::Acquire),
+        None => return false,
+    };
+    if sink == 0 {
+        return false;
+    }
+    let sink = unsafe { mem::transmute::<usize, SinkFn>(sink) };
+    sink(bytes);
+    true
+}
+
+/// Writes `value` to the redirection sink of the log port `port` in the ITM
+/// byte order. Returns `false` if the port is not redirected.
+#[doc(hidden)]
+#[inline]
+pub fn redirect_u8(port: u8, value: u8) -> bool {
+    redirect(port, &value.to_le_bytes())
+}
+
+/// Writes `value` to the redirection sink of the log port `port` in the ITM
+/// byte order. Returns `false` if the port is not redirected.
+#[doc(hidden)]
+#[inline]
+pub fn redirect_u16(port: u8, value: u16) -> bool {
+    redirect(port, &value.to_le_bytes())
+}
+
+/// Writes `value` to the redirection sink of the log port `port` in the ITM
+/// byte order. Returns `false` if the port is not redirected.
+#[doc(hidden)]
+#[inline]
+pub fn redirect_u32(port: u8, value: u32) -> bool {
+    redirect(port, &value.to_le_bytes())
+}
+
+fn set_redirect(port: u8, sink: Option<SinkFn>) {
+    REDIRECTS[usize::from(port)].store(sink.map_or(0, |sink| sink as usize), Ordering::Release);
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! io_write_to {
+    ($sink:expr, $($arg:tt)*) => {{
+        let _ = ::core::fmt::Write::write_fmt(
+            &mut $crate::io::FmtWriter::new(&mut $sink),
+            ::core::format_args!($($arg)*),
+        );
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! io_writeln_to {
+    ($sink:expr) => {
+        $crate::io_write_to!($sink, "\n")
+    };
+    ($sink:expr, $($arg:tt)*) => {
+        $crate::io_write_to!($sink, "{}\n", ::core::format_args!($($arg)*))
+    };
+}
+
+/// Writes formatted data into a [`Sink`].
+///
+/// Errors are ignored. See [the module level documentation](self) for details.
+#[doc(inline)]
+pub use crate::io_write_to as write_to;
+
+/// Writes formatted data into a [`Sink`], with a newline appended.
+///
+/// Errors are ignored. See [the module level documentation](self) for details.
+#[doc(inline)]
+pub use crate::io_writeln_to as writeln_to;
@@ -71,14 +71,20 @@
 
 extern crate alloc;
 
+pub mod buildinfo;
+pub mod deadline;
+pub mod debug;
 pub mod drv;
 pub mod fib;
+pub mod io;
 pub mod map;
 pub mod proc_loop;
 pub mod processor;
 pub mod reg;
 pub mod sv;
 pub mod swo;
+pub mod sync;
+pub mod system;
 pub mod thr;
 
 mod rt;
@@ -189,6 +189,8 @@ reg! {
             PRI_SYS_TICK => { offset => 24; width => 8; traits => { RRRegField WWRegField } };
             /// Priority of system handler 14, PendSV.
             PRI_PEND_SV => { offset => 16; width => 8; traits => { RRRegField WWRegField } };
+            /// Priority of system handler 12, DebugMonitor.
+            PRI_DEBUG_MONITOR => { offset => 0; width => 8; traits => { RRRegField WWRegField } };
         };
     };
 }
@@ -73,6 +73,64 @@ pub fn self_reset() -> ! {
     }
 }
 
+/// Enables the DWT cycle counter.
+///
+/// Sets the `TRCENA` bit of the `SCB_DEMCR` register and the `CYCCNTENA` bit of
+/// the `DWT_CTRL` register. The cycle counter is disabled from reset, and
+/// usually only a debugger enables it, so an application relying on
+/// [`cycle_count`] should call this function during initialization.
+#[inline]
+pub fn enable_cycle_counter() {
+    #[cfg(feature = "std")]
+    return unimplemented!();
+    #[cfg(not(feature = "std"))]
+    unsafe {
+        use crate::{
+            map::reg::{dwt, scb},
+            reg::prelude::*,
+        };
+        use drone_core::token::Token;
+        scb::Demcr::<Srt>::take().modify(|r| r.set_trcena());
+        dwt::Ctrl::<Srt>::take().modify(|r| r.set_cyccntena());
+    }
+}
+
+/// Returns `true` if the DWT cycle counter is running.
+///
+/// See [`enable_cycle_counter`].
+#[inline]
+pub fn is_cycle_counter_enabled() -> bool {
+    #[cfg(feature = "std")]
+    return unimplemented!();
+    #[cfg(not(feature = "std"))]
+    unsafe {
+        use crate::{
+            map::reg::{dwt, scb},
+            reg::prelude::*,
+        };
+        use drone_core::token::Token;
+        scb::Demcr::<Urt>::take().load().trcena() && dwt::Ctrl::<Urt>::take().load().cyccntena()
+    }
+}
+
+/// Returns the current value of the DWT cycle counter.
+///
+/// The cycle counter should be enabled beforehand with
+/// [`enable_cycle_counter`], otherwise the returned value doesn't change. The
+/// counter wraps around on overflow, so intervals should be computed with
+/// `wrapping_sub`.
+#[inline]
+pub fn cycle_count() -> u32 {
+    #[cfg(feature = "std")]
+    return unimplemented!();
+    #[cfg(not(feature = "std"))]
+    unsafe {
+        use crate::{map::reg::dwt, reg::prelude::*};
+        use drone_core::token::Token;
+        dwt::Cyccnt::<Urt>::take().load().cyccnt()
+    }
+}
+
 /// Spins the `cycles` number of processor cycles in a loop.
 #[inline(always)]
 pub fn spin(cycles: u32) {
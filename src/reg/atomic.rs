@@ -81,7 +81,7 @@ where
     fn write_bits(&self, bits: <<Self::Reg as Reg<T>>::Val as Bitfield>::Bits);
 }
 
-pub trait AtomicBits: Sized {
+pub trait AtomicBits: Copy + Into<u32> {
     unsafe fn load_excl(address: usize) -> Self;
 
     unsafe fn store_excl(self, address: usize) -> bool;
@@ -102,8 +102,10 @@ where
     {
         loop {
             let mut val = unsafe { self.hold(load_excl::<T, Self>()) };
+            let old = val.val().bits();
             f(&mut val);
             if unsafe { store_excl::<T, Self>(val.val()) } {
+                audit::<T, Self>(old, val.val().bits());
                 break;
             }
         }
@@ -116,8 +118,11 @@ where
     {
         loop {
             let mut val = unsafe { load_excl::<T, Self>() };
+            let old = val.bits();
             f(self, &mut val);
+            let new = val.bits();
             if unsafe { store_excl::<T, Self>(val) } {
+                audit::<T, Self>(old, new);
                 break;
             }
         }
@@ -138,8 +143,11 @@ where
     {
         loop {
             let mut val = unsafe { load_excl::<T, Self::Reg>() };
+            let old = val.bits();
             f(&mut val);
+            let new = val.bits();
             if unsafe { store_excl::<T, Self::Reg>(val) } {
+                audit::<T, Self::Reg>(old, new);
                 break;
             }
         }
@@ -206,6 +214,18 @@ where
     unsafe { val.bits().store_excl(R::ADDRESS) }
 }
 
+#[cfg_attr(not(feature = "reg-audit"), allow(unused_variables))]
+#[inline]
+fn audit<T, R>(old: <R::Val as Bitfield>::Bits, new: <R::Val as Bitfield>::Bits)
+where
+    T: RegAtomic,
+    R: Reg<T>,
+    <R::Val as Bitfield>::Bits: AtomicBits,
+{
+    #[cfg(feature = "reg-audit")]
+    super::audit::record(R::ADDRESS, old.into(), new.into());
+}
+
 macro_rules! atomic_bits {
     ($type:ty, $ldrex:expr, $strex:expr) => {
         impl AtomicBits for $type {
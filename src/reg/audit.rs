@@ -0,0 +1,97 @@
+//! Register write audit log.
+//!
+//! When `reg-audit` feature is enabled, register writes made by the atomic
+//! read-modify-write operations of this crate are recorded into a RAM ring
+//! buffer of the last [`LOG_LEN`] writes. The log can be dumped, e.g. from a
+//! fault handler, to find out which code has touched a register.
+//!
+//! Only the following operations on `Srt` and `Crt` tokens are recorded:
+//!
+//! * [`RwRegAtomic::modify`](crate::reg::RwRegAtomic::modify) and
+//!   [`RwRegAtomic::modify_reg`](crate::reg::RwRegAtomic::modify_reg)
+//! * [`WRwRegFieldAtomic::modify`](crate::reg::field::WRwRegFieldAtomic::modify)
+//! * [`set_bit`](crate::reg::field::WRwRegFieldBitAtomic::set_bit),
+//!   [`clear_bit`](crate::reg::field::WRwRegFieldBitAtomic::clear_bit),
+//!   [`toggle_bit`](crate::reg::field::WRwRegFieldBitAtomic::toggle_bit)
+//! * [`write_bits`](crate::reg::field::WRwRegFieldBitsAtomic::write_bits)
+//!
+//! All other writes are not recorded. This includes `store`, `store_val`,
+//! `store_reg`, and `reset` through any token, and `modify` through `Urt`
+//! tokens, which are implemented by `drone_core`. Bit-band writes are not
+//! recorded either, as they don't read the previous value.
+//!
+//! The timestamps are taken from [`processor::cycle_count`].
+
+use crate::processor;
+use core::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+/// Number of entries in the log.
+pub const LOG_LEN: usize = 32;
+
+/// A recorded register write.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegWrite {
+    /// The register address.
+    pub address: usize,
+    /// The register value before the write.
+    pub old: u32,
+    /// The written value.
+    pub new: u32,
+    /// The DWT cycle counter value at the moment of the write.
+    pub cycle: u32,
+}
+
+struct Entry {
+    address: AtomicUsize,
+    old: AtomicU32,
+    new: AtomicU32,
+    cycle: AtomicU32,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: Entry = Entry {
+    address: AtomicUsize::new(0),
+    old: AtomicU32::new(0),
+    new: AtomicU32::new(0),
+    cycle: AtomicU32::new(0),
+};
+
+static LOG: [Entry; LOG_LEN] = [EMPTY; LOG_LEN];
+static HEAD: AtomicUsize = AtomicUsize::new(0);
+
+/// Calls `f` for each recorded write, from the oldest to the newest.
+///
+/// An entry may be inconsistent if it's being written concurrently.
+pub fn for_each<F: FnMut(RegWrite)>(mut f: F) {
+    let head = HEAD.load(Ordering::Acquire);
+    for seq in head.saturating_sub(LOG_LEN)..head {
+        let entry = &LOG[seq % LOG_LEN];
+        f(RegWrite {
+            address: entry.address.load(Ordering::Relaxed),
+            old: entry.old.load(Ordering::Relaxed),
+            new: entry.new.load(Ordering::Relaxed),
+            cycle: entry.cycle.load(Ordering::Relaxed),
+        });
+    }
+}
+
+/// Prints the recorded writes to the standard error stream, from the oldest to
+/// the newest.
+pub fn dump() {
+    for_each(|RegWrite { address, old, new, cycle }| {
+        eprintln!("[{}] {:#010X}: {:#010X} -> {:#010X}", cycle, address, old, new);
+    });
+}
+
+/// Discards all recorded writes.
+pub fn clear() {
+    HEAD.store(0, Ordering::Release);
+}
+
+pub(crate) fn record(address: usize, old: u32, new: u32) {
+    let entry = &LOG[HEAD.fetch_add(1, Ordering::AcqRel) % LOG_LEN];
+    entry.address.store(address, Ordering::Relaxed);
+    entry.old.store(old, Ordering::Relaxed);
+    entry.new.store(new, Ordering::Relaxed);
+    entry.cycle.store(processor::cycle_count(), Ordering::Relaxed);
+}
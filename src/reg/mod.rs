@@ -32,6 +32,8 @@
 //! | [`modify`](RwRegAtomic::modify)         | read-write | Srt, Crt |
 //! | [`modify_reg`](RwRegAtomic::modify_reg) | read-write | Srt, Crt |
 
+#[cfg(feature = "reg-audit")]
+pub mod audit;
 pub mod field;
 pub mod marker;
 pub mod prelude;
@@ -39,10 +41,14 @@ pub mod prelude;
 mod atomic;
 #[cfg(feature = "bit-band")]
 mod bit_band;
+mod wait;
 
 #[doc(no_inline)]
 pub use drone_core::reg::*;
 
-pub use self::atomic::RwRegAtomic;
 #[cfg(feature = "bit-band")]
 pub use self::bit_band::{RegBitBand, BIT_BAND_BASE, BIT_BAND_WIDTH};
+pub use self::{
+    atomic::RwRegAtomic,
+    wait::{wait_for, wait_for_or},
+};
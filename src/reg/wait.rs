@@ -0,0 +1,57 @@
+use crate::{
+    fib,
+    reg::{field::RRRegFieldBit, tag::Crt},
+    thr::prelude::*,
+};
+use core::future::Future;
+
+/// Returns a future, which resolves when the bit `field` becomes set.
+///
+/// The bit is checked on each run of the thread `thr`, which is usually bound
+/// to the interrupt of the peripheral. Thus this future is a building block for
+/// drivers of the peripherals, which signal their state with status flags.
+///
+/// Timeouts can be implemented by racing this future with
+/// [`Timer::sleep`](crate::drv::timer::Timer::sleep).
+///
+/// See also [`wait_for_or`].
+pub fn wait_for<T, F>(thr: T, field: F) -> impl Future<Output = ()>
+where
+    T: ThrToken,
+    F: RRRegFieldBit<Crt> + Send + 'static,
+{
+    thr.add_future(fib::new_fn(
+        move || {
+            if field.read_bit() { fib::Complete(()) } else { fib::Yielded(()) }
+        },
+    ))
+}
+
+/// Returns a future, which resolves when the bit `field` becomes set, or when
+/// `check_err` returns an error.
+///
+/// `check_err` is called on each run of the thread `thr` before checking the
+/// bit, so an error condition short-circuits the waiting.
+///
+/// See also [`wait_for`].
+pub fn wait_for_or<T, F, E, C>(
+    thr: T,
+    field: F,
+    mut check_err: C,
+) -> impl Future<Output = Result<(), E>>
+where
+    T: ThrToken,
+    F: RRRegFieldBit<Crt> + Send + 'static,
+    E: Send + 'static,
+    C: FnMut() -> Option<E> + Send + 'static,
+{
+    thr.add_future(fib::new_fn(move || {
+        if let Some(err) = check_err() {
+            fib::Complete(Err(err))
+        } else if field.read_bit() {
+            fib::Complete(Ok(()))
+        } else {
+            fib::Yielded(())
+        }
+    }))
+}
@@ -1,6 +1,11 @@
-use crate::processor;
+use crate::{debug, processor};
 
 #[no_mangle]
 extern "C" fn drone_self_reset() -> ! {
+    if debug::is_halt_on_reset() {
+        loop {
+            debug::breakpoint();
+        }
+    }
     processor::self_reset()
 }
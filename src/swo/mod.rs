@@ -8,14 +8,20 @@
 
 mod port;
 
-pub use self::port::Port;
+#[cfg(feature = "std")]
+pub use self::port::test_capture;
+pub use self::port::{BoundedPort, Port};
 
 use crate::{
+    debug,
     map::reg::{dwt, itm, tpiu},
     processor,
     reg::prelude::*,
 };
-use core::ptr::read_volatile;
+use core::{
+    ptr::read_volatile,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 use drone_core::token::Token;
 
 /// Number of ports.
@@ -24,13 +30,19 @@ pub const PORTS_COUNT: u8 = 32;
 const ITM_TER: usize = 0xE000_0E00;
 const ITM_TCR: usize = 0xE000_0E80;
 
+static DROPPED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
 /// Returns `true` if the debug probe is connected and listening to the ITM
 /// output.
+///
+/// The ITM stays enabled after the debug probe is disconnected, so this
+/// function also checks that a debugger is attached. See
+/// [`debug::is_attached`].
 #[inline]
 pub fn is_enabled() -> bool {
     #[cfg(feature = "std")]
     return unimplemented!();
-    unsafe { read_volatile(ITM_TCR as *const u32) & 1 != 0 }
+    debug::is_attached() && unsafe { read_volatile(ITM_TCR as *const u32) & 1 != 0 }
 }
 
 /// Returns `true` if the debug probe is connected and listening to the output
@@ -39,7 +51,13 @@ pub fn is_enabled() -> bool {
 pub fn is_port_enabled(port: usize) -> bool {
     #[cfg(feature = "std")]
     return unimplemented!();
-    unsafe { read_volatile(ITM_TER as *const u32) & 1 << port != 0 }
+    is_enabled() && unsafe { read_volatile(ITM_TER as *const u32) & 1 << port != 0 }
+}
+
+/// Returns the number of bytes dropped by [`BoundedPort`] writes.
+#[inline]
+pub fn dropped_bytes() -> usize {
+    DROPPED_BYTES.load(Ordering::Relaxed)
 }
 
 /// Blocks until all pending packets are transmitted.
@@ -60,6 +78,9 @@ pub fn flush() {
 }
 
 /// Generates an ITM synchronization packet.
+///
+/// The packet is triggered by overwriting the DWT cycle counter, which breaks
+/// ongoing [`processor::cycle_count`] measurements.
 #[inline]
 pub fn sync() {
     #[cfg(feature = "std")]
@@ -107,6 +128,21 @@ pub fn update_prescaler(swoscaler: u32) {
 #[macro_export]
 macro_rules! swo_set_log {
     () => {
+        $crate::swo_set_log!(@impl |port, bytes| {
+            $crate::swo::Port::new(port).write_bytes(bytes);
+        });
+    };
+    (record_ceiling => $ceiling:expr) => {
+        $crate::swo_set_log!(@impl |port, bytes| {
+            $crate::swo::Port::new(port).write_record(bytes, $ceiling);
+        });
+    };
+    (max_writes => $max_writes:expr) => {
+        $crate::swo_set_log!(@impl |port, bytes| {
+            $crate::swo::Port::new(port).bounded($max_writes).write_bytes(bytes);
+        });
+    };
+    (@impl $write_bytes:expr) => {
         const _: () = {
             $crate::reg::assert_taken!("dwt_cyccnt");
             $crate::reg::assert_taken!("itm_tpr");
@@ -118,28 +154,36 @@ macro_rules! swo_set_log {
 
             #[no_mangle]
             extern "C" fn drone_log_is_enabled(port: u8) -> bool {
-                $crate::swo::is_port_enabled(port as usize)
+                $crate::io::is_redirected(port) || $crate::swo::is_port_enabled(port as usize)
             }
 
             #[no_mangle]
             extern "C" fn drone_log_write_bytes(port: u8, buffer: *const u8, count: usize) {
                 let bytes = unsafe { ::core::slice::from_raw_parts(buffer, count) };
-                $crate::swo::Port::new(port).write_bytes(bytes);
+                if !$crate::io::redirect(port, bytes) {
+                    ($write_bytes)(port, bytes);
+                }
             }
 
             #[no_mangle]
             extern "C" fn drone_log_write_u8(port: u8, value: u8) {
-                $crate::swo::Port::new(port).write(value);
+                if !$crate::io::redirect_u8(port, value) {
+                    $crate::swo::Port::new(port).write(value);
+                }
             }
 
             #[no_mangle]
             extern "C" fn drone_log_write_u16(port: u8, value: u16) {
-                $crate::swo::Port::new(port).write(value);
+                if !$crate::io::redirect_u16(port, value) {
+                    $crate::swo::Port::new(port).write(value);
+                }
             }
 
             #[no_mangle]
             extern "C" fn drone_log_write_u32(port: u8, value: u32) {
-                $crate::swo::Port::new(port).write(value);
+                if !$crate::io::redirect_u32(port, value) {
+                    $crate::swo::Port::new(port).write(value);
+                }
             }
 
             #[no_mangle]
@@ -169,5 +213,44 @@ macro_rules! swo_set_log {
 ///
 /// swo::set_log!();
 /// ```
+///
+/// By default, byte sequences are written with the lock-free
+/// [`Port::write_bytes`], so concurrent writes from different priority levels
+/// may interleave. With `record_ceiling` option, each byte sequence is written
+/// with [`Port::write_record`] using the given `BASEPRI` ceiling:
+///
+/// ```
+/// # #![feature(proc_macro_hygiene)]
+/// # use drone_cortexm::{cortexm_reg_tokens, swo};
+/// # cortexm_reg_tokens! {
+/// #     index => Regs;
+/// #     exclude => {
+/// #         dwt_cyccnt,
+/// #         itm_tpr, itm_tcr, itm_lar,
+/// #         tpiu_acpr, tpiu_sppr, tpiu_ffcr,
+/// #     }
+/// # }
+/// swo::set_log!(record_ceiling => 0x40);
+/// ```
+///
+/// With `max_writes` option, each byte sequence is written with
+/// [`BoundedPort`], so a single write call performs at most the given number
+/// of stimulus port writes. This bounds the time a logging call can spend
+/// waiting on the ITM FIFO. The remainder is dropped and counted by
+/// [`dropped_bytes`]:
+///
+/// ```
+/// # #![feature(proc_macro_hygiene)]
+/// # use drone_cortexm::{cortexm_reg_tokens, swo};
+/// # cortexm_reg_tokens! {
+/// #     index => Regs;
+/// #     exclude => {
+/// #         dwt_cyccnt,
+/// #         itm_tpr, itm_tcr, itm_lar,
+/// #         tpiu_acpr, tpiu_sppr, tpiu_ffcr,
+/// #     }
+/// # }
+/// swo::set_log!(max_writes => 16);
+/// ```
 #[doc(inline)]
 pub use crate::swo_set_log as set_log;
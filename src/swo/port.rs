@@ -1,7 +1,9 @@
-use super::PORTS_COUNT;
+use super::{DROPPED_BYTES, ITM_TER, PORTS_COUNT};
 use core::{
     fmt::{self, Write},
+    ptr::read_volatile,
     slice,
+    sync::atomic::Ordering,
 };
 
 const ADDRESS_BASE: usize = 0xE000_0000;
@@ -12,6 +14,17 @@ pub struct Port {
     address: usize,
 }
 
+/// ITM stimulus port handle, which bounds the number of stimulus port writes
+/// per call.
+///
+/// Created with [`Port::bounded`]. The bytes, which don't fit into the bound,
+/// are dropped and counted by [`dropped_bytes`](super::dropped_bytes).
+#[derive(Clone, Copy)]
+pub struct BoundedPort {
+    port: Port,
+    max_writes: usize,
+}
+
 pub trait PortWrite: Copy {
     fn port_write(address: usize, value: Self);
 }
@@ -28,6 +41,20 @@ impl Port {
         Self { address: ADDRESS_BASE + (usize::from(address) << 2) }
     }
 
+    /// Returns `true` if the ITM stimulus port is enabled.
+    ///
+    /// This is a single read of the `ITM_TER` register, which the debug probe
+    /// can change at any time. Writes to a disabled port are skipped, so
+    /// different categories of output can be assigned to different ports and
+    /// toggled live from the host.
+    #[inline]
+    pub fn is_enabled(self) -> bool {
+        #[cfg(feature = "std")]
+        return true;
+        let port = (self.address - ADDRESS_BASE) >> 2;
+        unsafe { read_volatile(ITM_TER as *const u32) & 1 << port != 0 }
+    }
+
     /// Writes a sequence of bytes to the ITM stimulus port.
     ///
     /// The resulting byte sequence that will be read from the port may be
@@ -35,9 +62,12 @@ impl Port {
     /// atomic byte sequences.
     #[inline]
     pub fn write_bytes(self, bytes: &[u8]) -> Self {
+        if !self.is_enabled() {
+            return self;
+        }
         fn write_slice<T: PortWrite>(port: Port, slice: &[T]) {
             for item in slice {
-                port.write(*item);
+                T::port_write(port.address, *item);
             }
         }
         let mut end = bytes.len();
@@ -60,6 +90,85 @@ impl Port {
         self
     }
 
+    /// Writes a sequence of bytes to the ITM stimulus port as a single record.
+    ///
+    /// Unlike [`Port::write_bytes`], the resulting byte sequence won't be
+    /// interleaved with concurrent writes from producers with priorities lower
+    /// than or equal to `ceiling`. This is achieved by raising `BASEPRI` to
+    /// `ceiling` for the duration of the write, so interrupts with higher
+    /// priorities are not affected. `ceiling` should be the highest priority
+    /// among the producers of the port, and must not be zero.
+    ///
+    /// Only the byte sequence passed to a single call is guarded. Note that
+    /// `drone_core` logging macros pass each formatting fragment separately,
+    /// so a formatted line can still be split between fragments.
+    ///
+    /// When the port has a single producer, prefer the lock-free
+    /// [`Port::write_bytes`].
+    #[inline]
+    pub fn write_record(self, bytes: &[u8], ceiling: u8) -> Self {
+        debug_assert!(ceiling != 0);
+        #[cfg(feature = "std")]
+        return self.write_bytes(bytes);
+        #[cfg(not(feature = "std"))]
+        unsafe {
+            let basepri: u32;
+            asm!(
+                "mrs {basepri}, basepri",
+                "msr basepri_max, {ceiling}",
+                basepri = out(reg) basepri,
+                ceiling = in(reg) u32::from(ceiling),
+                options(nostack, preserves_flags),
+            );
+            self.write_bytes(bytes);
+            asm!(
+                "msr basepri, {basepri}",
+                basepri = in(reg) basepri,
+                options(nostack, preserves_flags),
+            );
+        }
+        self
+    }
+
+    /// Writes a sequence of bytes to the ITM stimulus port, performing at most
+    /// `max_writes` stimulus port writes. Returns the remainder of `bytes`,
+    /// which wasn't written.
+    ///
+    /// Each stimulus port write carries up to 4 bytes and waits until the
+    /// port is ready to accept a new packet. Thus limiting the number of writes
+    /// bounds the worst-case execution time of this method, which is useful
+    /// for logging from latency-sensitive handlers. The caller may retry the
+    /// remainder later from a lower priority context.
+    ///
+    /// If the port is disabled, all of `bytes` are skipped.
+    #[inline]
+    pub fn write_bytes_bounded(self, mut bytes: &[u8], max_writes: usize) -> &[u8] {
+        if !self.is_enabled() {
+            return &[];
+        }
+        for _ in 0..max_writes {
+            match *bytes {
+                [] => break,
+                [b0, b1, b2, b3, ref rest @ ..] => {
+                    self.write(u32::from_le_bytes([b0, b1, b2, b3]));
+                    bytes = rest;
+                }
+                [b0, ref rest @ ..] => {
+                    self.write(b0);
+                    bytes = rest;
+                }
+            }
+        }
+        bytes
+    }
+
+    /// Returns a handle, which performs at most `max_writes` stimulus port
+    /// writes per call. See [`Port::write_bytes_bounded`].
+    #[inline]
+    pub fn bounded(self, max_writes: usize) -> BoundedPort {
+        BoundedPort { port: self, max_writes }
+    }
+
     /// Writes an atomic byte sequence to the ITM stimulus port. `T` can be one
     /// of `u8`, `u16`, `u32`.
     ///
@@ -68,7 +177,9 @@ impl Port {
     #[inline]
     pub fn write<T: PortWrite>(self, value: T) -> Self {
         let Self { address } = self;
-        T::port_write(address, value);
+        if self.is_enabled() {
+            T::port_write(address, value);
+        }
         self
     }
 }
@@ -81,10 +192,31 @@ impl Write for Port {
     }
 }
 
+impl BoundedPort {
+    /// Writes a sequence of bytes to the ITM stimulus port, performing at most
+    /// the configured number of writes. The remainder is dropped.
+    #[inline]
+    pub fn write_bytes(self, bytes: &[u8]) -> Self {
+        let rest = self.port.write_bytes_bounded(bytes, self.max_writes);
+        if !rest.is_empty() {
+            DROPPED_BYTES.fetch_add(rest.len(), Ordering::Relaxed);
+        }
+        self
+    }
+}
+
+impl Write for BoundedPort {
+    #[inline]
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        self.write_bytes(string.as_bytes());
+        Ok(())
+    }
+}
+
 impl PortWrite for u8 {
     fn port_write(address: usize, value: Self) {
         #[cfg(feature = "std")]
-        return unimplemented!();
+        return capture(address, &value.to_le_bytes());
         #[cfg(not(feature = "std"))]
         unsafe {
             asm!(
@@ -106,7 +238,7 @@ impl PortWrite for u8 {
 impl PortWrite for u16 {
     fn port_write(address: usize, value: Self) {
         #[cfg(feature = "std")]
-        return unimplemented!();
+        return capture(address, &value.to_le_bytes());
         #[cfg(not(feature = "std"))]
         unsafe {
             asm!(
@@ -128,7 +260,7 @@ impl PortWrite for u16 {
 impl PortWrite for u32 {
     fn port_write(address: usize, value: Self) {
         #[cfg(feature = "std")]
-        return unimplemented!();
+        return capture(address, &value.to_le_bytes());
         #[cfg(not(feature = "std"))]
         unsafe {
             asm!(
@@ -146,3 +278,26 @@ impl PortWrite for u32 {
         }
     }
 }
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static CAPTURE: core::cell::RefCell<std::collections::BTreeMap<u8, Vec<u8>>> =
+        core::cell::RefCell::new(std::collections::BTreeMap::new());
+}
+
+#[cfg(feature = "std")]
+fn capture(address: usize, bytes: &[u8]) {
+    let port = ((address - ADDRESS_BASE) >> 2) as u8;
+    CAPTURE.with(|capture| capture.borrow_mut().entry(port).or_default().extend_from_slice(bytes));
+}
+
+/// Returns and clears the bytes written to the ITM stimulus port number `port`
+/// by the current thread.
+///
+/// Available only with `std` feature, where ITM writes are captured into
+/// memory. As each test runs in its own thread, this allows asserting on the
+/// exact output produced by the code under test.
+#[cfg(feature = "std")]
+pub fn test_capture(port: u8) -> Vec<u8> {
+    CAPTURE.with(|capture| capture.borrow_mut().remove(&port).unwrap_or_default())
+}
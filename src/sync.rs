@@ -0,0 +1,122 @@
+//! Synchronization primitives.
+//!
+//! **NOTE** This module documentation should be viewed as a continuation of
+//! [the `drone_core` documentation](drone_core::sync).
+//!
+//! Platform-independent primitives belong to `drone_core`. This module only
+//! adds primitives, which depend on Cortex-M instructions: [`LazyInit`] waits
+//! for the initialization with `WFE` and signals it with `SEV`.
+
+#[doc(no_inline)]
+pub use drone_core::sync::*;
+
+use crate::processor;
+use core::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+const UNINIT: u8 = 0;
+const INITIALIZING: u8 = 1;
+const READY: u8 = 2;
+
+/// A cell, which can be initialized only once, safely shared between threads
+/// of different priorities.
+///
+/// This is a replacement for `static mut` with `MaybeUninit` pattern for
+/// sharing composed drivers with interrupt handlers.
+///
+/// # Examples
+///
+/// ```
+/// use drone_cortexm::sync::LazyInit;
+///
+/// struct Driver;
+///
+/// static DRIVER: LazyInit<Driver> = LazyInit::new();
+///
+/// assert!(DRIVER.get().is_none());
+/// assert!(DRIVER.init(Driver).is_ok());
+/// assert!(DRIVER.init(Driver).is_err());
+/// assert!(DRIVER.get().is_some());
+/// ```
+pub struct LazyInit<T> {
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+unsafe impl<T: Send + Sync> Sync for LazyInit<T> {}
+
+impl<T> LazyInit<T> {
+    /// Creates a new uninitialized cell.
+    pub const fn new() -> Self {
+        Self { state: AtomicU8::new(UNINIT), value: UnsafeCell::new(MaybeUninit::uninit()) }
+    }
+
+    /// Initializes the cell with `value`. Returns a reference to the stored
+    /// value.
+    ///
+    /// # Errors
+    ///
+    /// If the cell is already initialized, or is being initialized from another
+    /// context, returns `value` back as `Err`.
+    pub fn init(&self, value: T) -> Result<&T, T> {
+        if self
+            .state
+            .compare_exchange(UNINIT, INITIALIZING, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(value);
+        }
+        let slot = unsafe { (*self.value.get()).as_mut_ptr() };
+        unsafe { slot.write(value) };
+        self.state.store(READY, Ordering::Release);
+        #[cfg(not(feature = "std"))]
+        processor::send_event();
+        Ok(unsafe { &*slot })
+    }
+
+    /// Returns a reference to the stored value, or `None` if the cell is not
+    /// initialized yet.
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        if self.is_initialized() { Some(unsafe { &*(*self.value.get()).as_ptr() }) } else { None }
+    }
+
+    /// Returns a reference to the stored value, waiting for an event with
+    /// `WFE` until the cell is initialized from another context.
+    ///
+    /// The initializing context must be able to run while this method waits.
+    /// E.g. waiting from an interrupt handler, which preempted the initializing
+    /// thread, results in a deadlock.
+    pub fn get_blocking(&self) -> &T {
+        loop {
+            if let Some(value) = self.get() {
+                break value;
+            }
+            processor::wait_for_event();
+        }
+    }
+
+    /// Returns `true` if the cell is initialized.
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.state.load(Ordering::Acquire) == READY
+    }
+}
+
+impl<T> Default for LazyInit<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LazyInit<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == READY {
+            unsafe { self.value.get_mut().as_mut_ptr().drop_in_place() };
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! System-wide operations.
+
+#![cfg_attr(feature = "std", allow(unreachable_code, unused_variables))]
+
+use crate::{
+    map::reg::{scb, stk},
+    processor,
+    reg::prelude::*,
+    swo, thr,
+};
+use drone_core::token::Token;
+
+/// The final state of the system after [`shutdown`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Request a system reset.
+    Reset,
+    /// Enter the deep sleep state. The exact low-power mode (e.g. STOP or
+    /// STANDBY) is selected by the device-specific power controller
+    /// configuration.
+    ///
+    /// All interrupts and SysTick are disabled before entering the deep sleep
+    /// state, so the system can be woken up only by a reset-type event, e.g. a
+    /// STANDBY wakeup pin or the reset pin.
+    DeepSleep,
+}
+
+/// A driver that can be parked before [`shutdown`].
+pub trait Park {
+    /// Brings the peripheral into a safe idle state. E.g. stops timers,
+    /// disables DMA requests, feeds a watchdog for the last time.
+    fn park(&mut self);
+}
+
+/// Shuts down the system gracefully.
+///
+/// The shutdown sequence is:
+///
+/// 1. All interrupts are masked, so no thread will ever run its routines
+///    again.
+/// 2. The [`Park::park`] hook is called for each of `drivers`, in order.
+/// 3. Pending SWO packets are flushed.
+/// 4. The system is reset or put into the deep sleep state according to
+///    `mode`.
+///
+/// Routines and fibers attached to threads are not cancelled, nor dropped.
+/// They just never run again, because of the masked interrupts. Resources
+/// owned by them, which need releasing, should be released by the
+/// [`Park::park`] hooks.
+///
+/// # Examples
+///
+/// ```no_run
+/// use drone_cortexm::system::{self, Mode, Park};
+///
+/// struct Led;
+///
+/// impl Park for Led {
+///     fn park(&mut self) {
+///         // Turn the LED off.
+///     }
+/// }
+///
+/// let mut led = Led;
+/// system::shutdown(Mode::DeepSleep, &mut [&mut led]);
+/// ```
+pub fn shutdown(mode: Mode, drivers: &mut [&mut dyn Park]) -> ! {
+    #[cfg(feature = "std")]
+    return unimplemented!();
+    #[cfg(not(feature = "std"))]
+    unsafe {
+        asm!("cpsid i", options(nostack, preserves_flags));
+    }
+    for driver in drivers.iter_mut() {
+        driver.park();
+    }
+    swo::flush();
+    match mode {
+        Mode::Reset => processor::self_reset(),
+        Mode::DeepSleep => {
+            // PRIMASK prevents any handler from clearing a pending interrupt,
+            // so each pending interrupt would wake the processor up at once.
+            thr::disable_all_unchecked();
+            let stk_ctrl = unsafe { stk::Ctrl::<Srt>::take() };
+            stk_ctrl.reset();
+            let scb_icsr = unsafe { scb::Icsr::<Srt>::take() };
+            scb_icsr.store(|r| r.set_pendstclr());
+            let scb_scr = unsafe { scb::Scr::<Srt>::take() };
+            scb_scr.sleepdeep.set_bit();
+            loop {
+                processor::wait_for_int();
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+#![cfg_attr(feature = "std", allow(unreachable_code))]
+
+use crate::{map::reg::scb, processor, reg::prelude::*};
+use core::sync::atomic::{AtomicU32, Ordering};
+use drone_core::token::Token;
+
+/// Wakeup statistics collected by [`idle_loop`].
+///
+/// Wakeups are counted per exception number of the highest priority pending
+/// exception at the moment of wakeup. Interrupt numbers are offset by 16.
+/// Exceptions with numbers greater than or equal to `N`, and wakeups without a
+/// pending exception (e.g. on an event or a debug request), are counted as
+/// unknown.
+///
+/// To attribute a wakeup to all pending interrupts rather than the highest
+/// priority one, see [`pending_snapshot`](super::pending_snapshot).
+pub struct WakeStats<const N: usize> {
+    counts: [AtomicU32; N],
+    unknown: AtomicU32,
+}
+
+impl<const N: usize> WakeStats<N> {
+    /// Creates a new zeroed statistics.
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const fn new() -> Self {
+        const ZERO: AtomicU32 = AtomicU32::new(0);
+        Self { counts: [ZERO; N], unknown: ZERO }
+    }
+
+    /// Returns the number of wakeups caused by the exception number `exc_num`.
+    #[inline]
+    pub fn count(&self, exc_num: usize) -> u32 {
+        self.counts.get(exc_num).map_or(0, |count| count.load(Ordering::Relaxed))
+    }
+
+    /// Returns the number of wakeups with an unknown source.
+    #[inline]
+    pub fn unknown(&self) -> u32 {
+        self.unknown.load(Ordering::Relaxed)
+    }
+
+    /// Returns an iterator over exception numbers and wakeup counts of all
+    /// sources, which caused at least one wakeup.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, u32)> + '_ {
+        self.counts
+            .iter()
+            .map(|count| count.load(Ordering::Relaxed))
+            .enumerate()
+            .filter(|&(_, count)| count > 0)
+    }
+
+    fn record(&self, exc_num: usize) {
+        match self.counts.get(exc_num) {
+            Some(count) if exc_num != 0 => count.fetch_add(1, Ordering::Relaxed),
+            _ => self.unknown.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+}
+
+impl<const N: usize> Default for WakeStats<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs the idle loop, recording wakeup sources into `stats`.
+///
+/// This is an instrumented replacement for the typical
+/// `loop { processor::wait_for_int() }`. Interrupts are masked while the
+/// processor sleeps, so the pending exception can be sampled right after
+/// wakeup, before its handler is run. The sleep mode is selected by the
+/// `SLEEPDEEP` bit of the `SCB_SCR` register as usual.
+///
+/// # Examples
+///
+/// ```no_run
+/// use drone_cortexm::thr::{self, WakeStats};
+///
+/// static WAKE_STATS: WakeStats<64> = WakeStats::new();
+///
+/// thr::idle_loop(&WAKE_STATS);
+/// ```
+pub fn idle_loop<const N: usize>(stats: &WakeStats<N>) -> ! {
+    let icsr = unsafe { scb::Icsr::<Urt>::take() };
+    loop {
+        #[cfg(feature = "std")]
+        return unimplemented!();
+        #[cfg(not(feature = "std"))]
+        unsafe {
+            asm!("cpsid i", options(nostack, preserves_flags));
+        }
+        processor::wait_for_int();
+        stats.record(icsr.load().vectpending() as usize);
+        #[cfg(not(feature = "std"))]
+        unsafe {
+            asm!("cpsie i", options(nostack, preserves_flags));
+        }
+    }
+}
@@ -1,6 +1,13 @@
 #![cfg_attr(feature = "std", allow(dead_code, unreachable_code))]
 
-use crate::{map::reg::scb, reg::prelude::*};
+use crate::{
+    map::reg::scb,
+    reg::prelude::*,
+    thr::{
+        nvic::{enable_block_unchecked, NVIC_BLOCKS_MAX},
+        IntToken, NvicBlock,
+    },
+};
 use drone_core::token::Token;
 
 /// Threads initialization token.
@@ -85,6 +92,132 @@ pub fn init_extended<T: ThrsInitToken>(_token: T) -> (T::ThrTokens, ThrInitExten
     })
 }
 
+/// The first stage of the thread system initialization returned by
+/// [`init_stage1`].
+///
+/// All interrupts are masked with `PRIMASK` during this stage, so drivers can
+/// be composed and initial routines can be attached to threads without being
+/// preempted by half-configured interrupts. Interrupts armed with
+/// [`arm`](ThrInitStage1::arm) are enabled within the NVIC in one batch by
+/// [`enable_all`](ThrInitStage1::enable_all), which ends the stage and
+/// restores the previous `PRIMASK` state.
+///
+/// `PRIMASK` also masks the SVCall exception, so supervisor calls must not be
+/// made during this stage. E.g. resuming a stackful fiber, which switches
+/// contexts with [`sv::Switch`](crate::sv::Switch), escalates to HardFault.
+#[must_use]
+pub struct ThrInitStage1<T: ThrsInitToken> {
+    thr: T::ThrTokens,
+    armed: [u32; NVIC_BLOCKS_MAX],
+    primask: u32,
+}
+
+impl<T: ThrsInitToken> ThrInitStage1<T> {
+    /// Returns a reference to the set of thread tokens.
+    ///
+    /// The tokens can be used to attach routines to the threads, but the
+    /// threads will not run until the stage is finished. These are the full
+    /// thread tokens, so an interrupt enabled directly with
+    /// [`ThrNvic::enable`](crate::thr::ThrNvic::enable) bypasses arming. It
+    /// will still be held off by `PRIMASK` until the end of the stage.
+    #[inline]
+    pub fn thr(&self) -> &T::ThrTokens {
+        &self.thr
+    }
+
+    /// Arms the interrupt to be enabled at the end of the stage.
+    #[inline]
+    pub fn arm<I: IntToken>(&mut self, _int: I) {
+        self.armed[<I::NvicBlock as NvicBlock>::BLOCK_NUM] |= 1 << (I::INT_NUM & 0b1_1111);
+    }
+
+    /// Enables all armed interrupts, restores the `PRIMASK` state saved by
+    /// [`init_stage1`], and returns the set of thread tokens.
+    ///
+    /// Interrupts stay masked if they were masked before [`init_stage1`].
+    pub fn enable_all(self) -> T::ThrTokens {
+        for (block, &bits) in self.armed.iter().enumerate() {
+            if bits != 0 {
+                enable_block_unchecked(block, bits);
+            }
+        }
+        #[cfg(not(feature = "std"))]
+        unsafe {
+            asm!(
+                "msr primask, {primask}",
+                primask = in(reg) self.primask,
+                options(nostack, preserves_flags),
+            );
+        }
+        self.thr
+    }
+}
+
+/// Masks all interrupts, initializes the thread system, and returns the first
+/// stage of the initialization.
+///
+/// The previous `PRIMASK` state is saved and restored at the end of the stage.
+/// See [`ThrInitStage1`] for the restrictions of the stage.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #![feature(const_fn_fn_ptr_basics)]
+/// # #![feature(proc_macro_hygiene)]
+/// # use drone_core::token::Token;
+/// # thr::nvic! {
+/// #     thread => pub Thr {};
+/// #     local => pub ThrLocal {};
+/// #     index => Thrs;
+/// #     vtable => Vtable;
+/// #     init => ThrsInit;
+/// #     threads => {
+/// #         interrupts => {
+/// #             10: pub exti4;
+/// #         };
+/// #     };
+/// # }
+/// use drone_cortexm::{cortexm_reg_tokens, thr};
+///
+/// cortexm_reg_tokens! {
+///     index => Regs;
+///     exclude => {
+///         scb_ccr,
+///         mpu_type, mpu_ctrl, mpu_rnr, mpu_rbar, mpu_rasr,
+///     }
+/// }
+///
+/// fn handler(reg: Regs, thr_init: ThrsInit) {
+///     let mut stage = thr::init_stage1(thr_init);
+///     let exti4 = stage.thr().exti4;
+///     // Compose drivers and attach routines to `exti4` here.
+///     stage.arm(exti4);
+///     let thr = stage.enable_all();
+/// }
+///
+/// # fn main() {
+/// #     handler(unsafe { Regs::take() }, unsafe { ThrsInit::take() })
+/// # }
+/// ```
+#[inline]
+pub fn init_stage1<T: ThrsInitToken>(token: T) -> ThrInitStage1<T> {
+    #[cfg(feature = "std")]
+    let primask = 0;
+    #[cfg(not(feature = "std"))]
+    let primask: u32;
+    #[cfg(not(feature = "std"))]
+    unsafe {
+        asm!(
+            "mrs {primask}, primask",
+            "cpsid i",
+            primask = out(reg) primask,
+            options(nostack, preserves_flags),
+        );
+    }
+    let thr = init(token);
+    ThrInitStage1 { thr, armed: [0; NVIC_BLOCKS_MAX], primask }
+}
+
 /// Initializes the thread system and returns a set of thread tokens.
 ///
 /// # Examples
@@ -38,6 +38,11 @@
 //!             pub nmi;
 //!             /// All classes of fault.
 //!             pub hard_fault;
+//!             // Define a regular thread for the USAGE_FAULT exception with the declared
+//!             // priority 0x20. The macro doesn't apply the priority, it should still be
+//!             // set at run-time. `thr::dump_vectors` reports a mismatch.
+//!             /// Undefined instruction or illegal state.
+//!             pub priority(0x20) usage_fault;
 //!             // Define a naked handler for the SV_CALL exception. This inserts the function
 //!             // `sv_call_handler` directly to the vector table.
 //!             /// System service call.
@@ -56,6 +61,20 @@
 //!             // uses a custom handler `adc1_handler`.
 //!             /// ADC1 global interrupt.
 //!             18: pub outer(adc1_handler) adc1;
+//!             // Define a regular thread for the interrupt #23 with name `exti9_5`, which is
+//!             // guarded from interrupt storms by the `EXTI9_5_GUARD` static. See
+//!             // `StormGuard` for details.
+//!             /// EXTI Line[9:5] interrupts.
+//!             23: pub guard(EXTI9_5_GUARD) exti9_5;
+//!             // Define a thread for the interrupt #30 with name `tim4`, which has a fast
+//!             // handler `tim4_fast_handler`. The vector points to a generated wrapper,
+//!             // which calls the fast handler bypassing the thread dispatcher (after the
+//!             // storm guard check if `guard` is given). The thread itself runs only if
+//!             // the fast handler returns `true`. A `#[link_section]` attribute is applied
+//!             // to the generated wrapper, so it can be placed in RAM together with the
+//!             // fast handler.
+//!             /// TIM4 global interrupt.
+//!             30: pub fast(tim4_fast_handler) tim4;
 //!         };
 //!     };
 //! }
@@ -69,6 +88,12 @@
 //! // `extern` keyword.
 //! unsafe extern "C" fn sv_call_handler() {}
 //! unsafe fn adc1_handler(_thr: &Thr) {}
+//! fn tim4_fast_handler() -> bool {
+//!     false
+//! }
+//!
+//! // Define the interrupt storm guard: at most 100 interrupts within 8_000 cycles.
+//! static EXTI9_5_GUARD: thr::StormGuard = thr::StormGuard::new(100, 8_000);
 //!
 //! // Define and export the actual vector table with all handlers attached.
 //! #[no_mangle]
@@ -92,20 +117,31 @@
 
 pub mod prelude;
 
+mod idle;
 mod init;
 mod int;
 mod nvic;
 mod root;
+mod storm;
+mod vector;
 mod wake;
 
 #[doc(no_inline)]
 pub use drone_core::thr::*;
 
+pub(crate) use self::nvic::disable_all_unchecked;
+
 pub use self::{
-    init::{init, init_extended, ThrInitExtended, ThrsInitToken},
+    idle::{idle_loop, WakeStats},
+    init::{init, init_extended, init_stage1, ThrInitExtended, ThrInitStage1, ThrsInitToken},
     int::IntToken,
-    nvic::{NvicBlock, NvicIabr, NvicIcer, NvicIcpr, NvicIser, NvicIspr, ThrNvic},
+    nvic::{
+        pending_snapshot, NvicBlock, NvicIabr, NvicIcer, NvicIcpr, NvicIser, NvicIspr, PendingMask,
+        ThrNvic,
+    },
     root::{FutureRootExt, StreamRootExt, StreamRootWait},
+    storm::StormGuard,
+    vector::{dump_vectors, Vector, VectorKind},
 };
 
 /// Defines a thread pool driven by NVIC (Nested Vector Interrupt Controller).
@@ -1,3 +1,5 @@
+#![cfg_attr(feature = "std", allow(unreachable_code))]
+
 use crate::thr::IntToken;
 use core::{
     marker::PhantomData,
@@ -11,6 +13,9 @@ const NVIC_ICPR: usize = 0xE000_E280;
 const NVIC_IABR: usize = 0xE000_E300;
 const NVIC_IPR: usize = 0xE000_E400;
 
+/// Maximum number of NVIC register blocks.
+pub(crate) const NVIC_BLOCKS_MAX: usize = 16;
+
 macro_rules! nvic_reg {
     ($doc:expr, $name:ident, $base:expr) => {
         #[doc = $doc]
@@ -241,6 +246,96 @@ trait NvicReg<T: NvicBlock>: Sized {
 
 impl<T: IntToken> ThrNvic for T {}
 
+/// A snapshot of the NVIC pending interrupts.
+///
+/// See [`pending_snapshot`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PendingMask([u32; NVIC_BLOCKS_MAX]);
+
+impl PendingMask {
+    /// Returns `true` if the interrupt number `int_num` is pending.
+    #[inline]
+    pub fn is_pending(&self, int_num: u16) -> bool {
+        let int_num = usize::from(int_num);
+        self.0.get(int_num >> 5).map_or(false, |block| block & 1 << (int_num & 0b1_1111) != 0)
+    }
+
+    /// Marks the interrupt number `int_num` as pending.
+    ///
+    /// # Panics
+    ///
+    /// If `int_num` is out of the NVIC range.
+    #[inline]
+    pub fn insert(&mut self, int_num: u16) {
+        let int_num = usize::from(int_num);
+        self.0[int_num >> 5] |= 1 << (int_num & 0b1_1111);
+    }
+
+    /// Returns `true` if the interrupt `T` is pending.
+    #[inline]
+    pub fn is_int_pending<T: IntToken>(&self) -> bool {
+        self.is_pending(T::INT_NUM)
+    }
+
+    /// Returns `true` if no interrupts are pending.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|&block| block == 0)
+    }
+
+    /// Returns the interrupts pending in `self`, but not in `before`.
+    ///
+    /// This is useful for attributing a wakeup to specific interrupts by
+    /// diffing snapshots taken before and after `WFI`.
+    #[inline]
+    pub fn newly_pending(&self, before: &Self) -> Self {
+        let mut mask = *self;
+        for (block, before) in mask.0.iter_mut().zip(&before.0) {
+            *block &= !before;
+        }
+        mask
+    }
+
+    /// Returns an iterator over the pending interrupt numbers.
+    pub fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        self.0.iter().enumerate().flat_map(|(idx, &block)| {
+            (0..32).filter(move |bit| block & 1 << bit != 0).map(move |bit| (idx << 5 | bit) as u16)
+        })
+    }
+}
+
+/// Takes a snapshot of the NVIC pending interrupts.
+///
+/// The snapshot is not atomic across NVIC register blocks. To get a consistent
+/// picture of the interrupts, which woke the processor up, take the snapshot
+/// with interrupts masked right after `WFI`, like [`idle_loop`](super::idle_loop)
+/// does.
+pub fn pending_snapshot() -> PendingMask {
+    #[cfg(feature = "std")]
+    return unimplemented!();
+    let mut mask = PendingMask::default();
+    for (idx, block) in mask.0.iter_mut().enumerate() {
+        *block = unsafe { read_volatile((NVIC_ISPR as *const u32).add(idx)) };
+    }
+    mask
+}
+
+/// Enables interrupts set in `bits` within the NVIC register block number
+/// `block`.
+pub(crate) fn enable_block_unchecked(block: usize, bits: u32) {
+    unsafe { write_volatile((NVIC_ISER as *mut u32).add(block), bits) };
+}
+
+/// Disables all interrupts within the NVIC and clears their pending state.
+pub(crate) fn disable_all_unchecked() {
+    for block in 0..NVIC_BLOCKS_MAX {
+        unsafe {
+            write_volatile((NVIC_ICER as *mut u32).add(block), 0xFFFF_FFFF);
+            write_volatile((NVIC_ICPR as *mut u32).add(block), 0xFFFF_FFFF);
+        }
+    }
+}
+
 const fn block_offset<T: IntToken>() -> usize {
     T::INT_NUM as usize & 0b1_1111
 }
@@ -0,0 +1,111 @@
+use crate::{processor, thr::ThrNvic};
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// Interrupt storm guard.
+///
+/// Counts invocations of an interrupt within a window of processor cycles. If
+/// the count exceeds the limit, the interrupt is considered to be in a storm
+/// (e.g. a floating input line firing continuously), and it is disabled within
+/// the NVIC, so it can't starve the rest of the system. Each storm is reported
+/// to the standard error log stream.
+///
+/// A guard is attached to an interrupt thread with the `guard` modifier of
+/// [`thr::nvic!`](crate::thr::nvic) macro:
+///
+/// ```
+/// # #![feature(const_fn_fn_ptr_basics)]
+/// # fn main() {}
+/// use drone_cortexm::thr::{self, StormGuard};
+///
+/// // Allow at most 1000 interrupts within 72_000 cycles.
+/// static EXTI4_GUARD: StormGuard = StormGuard::new(1000, 72_000);
+///
+/// thr::nvic! {
+///     thread => pub Thr {};
+///     local => pub ThrLocal {};
+///     index => pub Thrs;
+///     vtable => pub Vtable;
+///     init => pub ThrsInit;
+///     threads => {
+///         interrupts => {
+///             10: pub guard(EXTI4_GUARD) exti4;
+///         };
+///     };
+/// }
+/// ```
+///
+/// The guard measures time with the DWT cycle counter, which is stopped from
+/// reset and is usually started only by a debugger. The application should
+/// start it with [`processor::enable_cycle_counter`] during initialization.
+/// While the counter is stopped, the guard is inactive: [`check`] lets all
+/// interrupts through and doesn't count them.
+///
+/// [`check`]: StormGuard::check
+pub struct StormGuard {
+    limit: u32,
+    window: u32,
+    start: AtomicU32,
+    count: AtomicU32,
+    storms: AtomicU32,
+}
+
+impl StormGuard {
+    /// Creates a new guard, which allows at most `limit` interrupts within
+    /// `window` cycles.
+    pub const fn new(limit: u32, window: u32) -> Self {
+        Self {
+            limit,
+            window,
+            start: AtomicU32::new(0),
+            count: AtomicU32::new(0),
+            storms: AtomicU32::new(0),
+        }
+    }
+
+    /// Registers an invocation of the interrupt `T`. Returns `false` if the
+    /// interrupt has been disabled because of a storm.
+    ///
+    /// Always returns `true` if the DWT cycle counter is stopped.
+    ///
+    /// # Safety
+    ///
+    /// * This function doesn't check for the interrupt token ownership.
+    /// * Must be called only from the handler of the interrupt `T`.
+    pub unsafe fn check<T: ThrNvic>(&self) -> bool {
+        if !processor::is_cycle_counter_enabled() || self.record(processor::cycle_count()) {
+            return true;
+        }
+        let int = unsafe { T::take() };
+        int.disable();
+        int.clear_pending();
+        eprintln!("IRQ storm detected on interrupt #{}; interrupt disabled", T::INT_NUM);
+        false
+    }
+
+    /// Registers an invocation at the cycle counter value `now`. Returns
+    /// `false` if the invocation exceeds the limit, in which case the storm is
+    /// counted and the window is restarted.
+    ///
+    /// This is the counting part of [`check`](StormGuard::check), which doesn't
+    /// touch the NVIC.
+    pub fn record(&self, now: u32) -> bool {
+        let mut count = self.count.load(Ordering::Relaxed) + 1;
+        if now.wrapping_sub(self.start.load(Ordering::Relaxed)) > self.window {
+            self.start.store(now, Ordering::Relaxed);
+            count = 1;
+        }
+        if count > self.limit {
+            self.count.store(0, Ordering::Relaxed);
+            self.storms.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        self.count.store(count, Ordering::Relaxed);
+        true
+    }
+
+    /// Returns the number of detected storms.
+    #[inline]
+    pub fn storms(&self) -> u32 {
+        self.storms.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,112 @@
+#![cfg_attr(feature = "std", allow(unreachable_code))]
+
+use core::ptr::read_volatile;
+
+const SCB_SHPR1: usize = 0xE000_ED18;
+const NVIC_IPR: usize = 0xE000_E400;
+
+/// A vector configured by [`thr::nvic!`](crate::thr::nvic) macro.
+///
+/// The vector table type generated by the macro provides the list of all
+/// configured vectors as `VECTORS` associated constant. It can be used to
+/// verify that the deployed configuration matches the design, e.g. by dumping
+/// it with [`dump_vectors`].
+///
+/// Resource sets are not included, because the macro doesn't know which
+/// resources a thread uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Vector {
+    /// The exception number. Interrupt numbers are offset by 16.
+    pub exc_num: u16,
+    /// The thread name.
+    pub thread: &'static str,
+    /// The handler kind.
+    pub kind: VectorKind,
+    /// The handler path. For regular threads it's the thread name.
+    pub handler: &'static str,
+    /// The declared priority, given with the `priority` modifier. The macro
+    /// doesn't apply it; the priority should still be set at run-time. Use
+    /// [`Vector::live_priority`] to check that it was.
+    pub priority: Option<u8>,
+    /// The path of the attached [`StormGuard`](crate::thr::StormGuard).
+    pub guard: Option<&'static str>,
+}
+
+impl Vector {
+    /// Reads the priority of the vector currently configured in the `SCB_SHPRx`
+    /// or `NVIC_IPRx` register. Returns `None` for the exceptions with a fixed
+    /// priority (reset, NMI, and HardFault).
+    ///
+    /// The unimplemented low-order priority bits read as zero, so the declared
+    /// priority should have them cleared to match.
+    pub fn live_priority(&self) -> Option<u8> {
+        #[cfg(feature = "std")]
+        return unimplemented!();
+        let exc_num = usize::from(self.exc_num);
+        let address = match exc_num {
+            0..=3 => return None,
+            4..=15 => SCB_SHPR1 + exc_num - 4,
+            _ => NVIC_IPR + exc_num - 16,
+        };
+        Some(unsafe { read_volatile(address as *const u8) })
+    }
+}
+
+/// The kind of a vector handler.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VectorKind {
+    /// A regular thread.
+    Inner,
+    /// A thread with a custom handler.
+    Outer,
+    /// A thread with a fast handler, which is called by the generated wrapper
+    /// before the thread.
+    Fast,
+    /// A naked handler inserted directly to the vector table.
+    Naked,
+}
+
+/// Prints `vectors` to the standard output, one vector per line.
+///
+/// For each vector with a declared priority, the live priority is read back
+/// from the hardware, and a mismatch is reported.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #![feature(const_fn_fn_ptr_basics)]
+/// use drone_cortexm::thr;
+///
+/// thr::nvic! {
+///     thread => pub Thr {};
+///     local => pub ThrLocal {};
+///     index => pub Thrs;
+///     vtable => pub Vtable;
+///     init => pub ThrsInit;
+///     threads => {
+///         exceptions => {
+///             pub sys_tick;
+///         };
+///     };
+/// }
+///
+/// # fn main() {
+/// thr::dump_vectors(Vtable::VECTORS);
+/// # }
+/// ```
+pub fn dump_vectors(vectors: &[Vector]) {
+    for vector in vectors {
+        let Vector { exc_num, thread, kind, handler, priority, guard } = vector;
+        print!("#{} {} {:?}({})", exc_num, thread, kind, handler);
+        if let Some(priority) = priority {
+            print!(" priority({:#04X})", priority);
+            if let Some(live) = vector.live_priority().filter(|live| live != priority) {
+                print!(" MISMATCH: live priority({:#04X})", live);
+            }
+        }
+        if let Some(guard) = guard {
+            print!(" guard({})", guard);
+        }
+        println!();
+    }
+}
@@ -0,0 +1,12 @@
+use drone_cortexm::buildinfo;
+
+buildinfo::embed!();
+
+#[test]
+fn get() {
+    let info = buildinfo::get();
+    assert_eq!(info.name, "drone-cortexm");
+    assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(info.git_hash, option_env!("DRONE_GIT_HASH"));
+    assert_eq!(info.timestamp, option_env!("DRONE_BUILD_TIMESTAMP"));
+}
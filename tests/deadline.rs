@@ -0,0 +1,16 @@
+use drone_cortexm::deadline::Deadline;
+
+#[test]
+fn record() {
+    let deadline = Deadline::new(100);
+    assert_eq!(deadline.budget(), 100);
+    assert!(deadline.record(50));
+    assert!(deadline.record(100));
+    assert_eq!(deadline.misses(), 0);
+    assert_eq!(deadline.worst(), 100);
+    assert!(!deadline.record(150));
+    assert!(deadline.record(70));
+    assert!(!deadline.record(120));
+    assert_eq!(deadline.misses(), 2);
+    assert_eq!(deadline.worst(), 150);
+}
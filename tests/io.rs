@@ -0,0 +1,28 @@
+use drone_cortexm::io;
+use std::cell::RefCell;
+
+thread_local! {
+    static OUTPUT: RefCell<Vec<u8>> = RefCell::new(Vec::new());
+}
+
+fn capture(bytes: &[u8]) {
+    OUTPUT.with(|output| output.borrow_mut().extend_from_slice(bytes));
+}
+
+#[test]
+fn redirect() {
+    assert!(!io::redirect(0, b"lost"));
+    io::set_stdout(Some(capture));
+    assert!(io::is_redirected(0));
+    assert!(!io::is_redirected(1));
+    assert!(io::redirect(0, b"x = "));
+    assert!(io::redirect_u8(0, 0x12));
+    assert!(io::redirect_u16(0, 0x1234));
+    assert!(io::redirect_u32(0, 0x1234_5678));
+    io::set_stdout(None);
+    assert!(!io::is_redirected(0));
+    assert!(!io::redirect(0, b"lost"));
+    OUTPUT.with(|output| {
+        assert_eq!(*output.borrow(), b"x = \x12\x34\x12\x78\x56\x34\x12");
+    });
+}
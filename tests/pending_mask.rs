@@ -0,0 +1,40 @@
+use drone_cortexm::thr::PendingMask;
+
+fn mask(int_nums: &[u16]) -> PendingMask {
+    let mut mask = PendingMask::default();
+    for &int_num in int_nums {
+        mask.insert(int_num);
+    }
+    mask
+}
+
+#[test]
+fn is_pending() {
+    let mask = mask(&[0, 31, 32, 95, 479]);
+    assert!(mask.is_pending(0));
+    assert!(mask.is_pending(31));
+    assert!(mask.is_pending(32));
+    assert!(mask.is_pending(95));
+    assert!(mask.is_pending(479));
+    assert!(!mask.is_pending(1));
+    assert!(!mask.is_pending(33));
+    assert!(!mask.is_pending(480));
+    assert!(!mask.is_empty());
+    assert!(PendingMask::default().is_empty());
+}
+
+#[test]
+fn iter() {
+    let mask = mask(&[479, 5, 64, 37]);
+    assert_eq!(mask.iter().collect::<Vec<_>>(), [5, 37, 64, 479]);
+    assert_eq!(PendingMask::default().iter().count(), 0);
+}
+
+#[test]
+fn newly_pending() {
+    let before = mask(&[3, 40, 100]);
+    let after = mask(&[3, 41, 100, 200]);
+    assert_eq!(after.newly_pending(&before), mask(&[41, 200]));
+    assert!(before.newly_pending(&before).is_empty());
+    assert_eq!(before.newly_pending(&PendingMask::default()), before);
+}
@@ -0,0 +1,33 @@
+use drone_cortexm::thr::StormGuard;
+
+#[test]
+fn limit() {
+    let guard = StormGuard::new(3, 100);
+    assert!(guard.record(10));
+    assert!(guard.record(20));
+    assert!(guard.record(30));
+    assert!(!guard.record(40));
+    assert_eq!(guard.storms(), 1);
+    assert!(guard.record(50));
+    assert_eq!(guard.storms(), 1);
+}
+
+#[test]
+fn window() {
+    let guard = StormGuard::new(2, 100);
+    assert!(guard.record(10));
+    assert!(guard.record(20));
+    assert!(guard.record(150));
+    assert!(guard.record(160));
+    assert!(!guard.record(170));
+    assert_eq!(guard.storms(), 1);
+}
+
+#[test]
+fn wrapping() {
+    let guard = StormGuard::new(1, 100);
+    assert!(guard.record(u32::MAX - 20));
+    assert!(!guard.record(10));
+    assert!(guard.record(200));
+    assert_eq!(guard.storms(), 1);
+}
@@ -0,0 +1,21 @@
+use core::fmt::Write;
+use drone_cortexm::swo::{self, Port};
+
+#[test]
+fn capture() {
+    Port::new(3).write_bytes(b"hello, world\n");
+    Port::new(3).write(0x1234_u16);
+    assert_eq!(swo::test_capture(3), b"hello, world\n\x34\x12");
+    assert_eq!(swo::test_capture(3), b"");
+    assert_eq!(swo::test_capture(4), b"");
+}
+
+#[test]
+fn bounded() {
+    Port::new(5).bounded(2).write_bytes(b"0123456789");
+    assert_eq!(swo::test_capture(5), b"01234567");
+    assert_eq!(swo::dropped_bytes(), 2);
+    write!(Port::new(5).bounded(1), "{}", "abcdef").unwrap();
+    assert_eq!(swo::test_capture(5), b"abcd");
+    assert_eq!(swo::dropped_bytes(), 4);
+}
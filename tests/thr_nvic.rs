@@ -44,10 +44,16 @@ thr::nvic! {
             pub outer(nmi_handler) nmi;
             /// Test doc attribute
             #[doc = "test attribute"]
+            pub priority(0x20) usage_fault;
+            /// Test doc attribute
+            #[doc = "test attribute"]
             pub naked(Sv::handler) sv_call;
             /// Test doc attribute
             #[doc = "test attribute"]
             pub sys_tick;
+            /// Test doc attribute
+            #[doc = "test attribute"]
+            pub fast(pend_sv_handler) pend_sv;
         };
         interrupts => {
             /// Test doc attribute
@@ -55,13 +61,30 @@ thr::nvic! {
             10: pub exti4;
             /// Test doc attribute
             #[doc = "test attribute"]
+            8: pub priority(0x40) guard(EXTI2_GUARD) exti2;
+            /// Test doc attribute
+            #[doc = "test attribute"]
             5: pub naked(rcc_handler) rcc;
+            /// Test doc attribute
+            #[link_section = ".text.thr_fast"]
+            3: pub fast(rtc_handler) rtc;
         };
     };
 }
 
+static EXTI2_GUARD: thr::StormGuard = thr::StormGuard::new(1000, 72_000);
+
 fn nmi_handler(_thr: &Thr) {}
 
+fn pend_sv_handler() -> bool {
+    true
+}
+
+#[link_section = ".text.thr_fast"]
+fn rtc_handler() -> bool {
+    false
+}
+
 extern "C" fn rcc_handler() {}
 
 sv::pool! {
@@ -83,7 +106,43 @@ fn new() {
 
 #[test]
 fn size() {
-    assert_eq!(Thr::COUNT, 3);
+    assert_eq!(Thr::COUNT, 7);
     assert_eq!(size_of::<Vtable>(), 208);
     assert_eq!(SERVICES.len(), 2);
 }
+
+#[test]
+fn vectors() {
+    let vectors = Vtable::VECTORS;
+    assert_eq!(vectors.len(), 9);
+    assert_eq!(vectors[0].exc_num, 2);
+    assert_eq!(vectors[0].kind, thr::VectorKind::Outer);
+    assert_eq!(vectors[0].handler, "nmi_handler");
+    assert_eq!(vectors[1].thread, "usage_fault");
+    assert_eq!(vectors[1].exc_num, 6);
+    assert_eq!(vectors[1].kind, thr::VectorKind::Inner);
+    assert_eq!(vectors[1].priority, ::std::option::Option::Some(0x20));
+    assert_eq!(vectors[2].exc_num, 11);
+    assert_eq!(vectors[2].handler, "Sv::handler");
+    assert_eq!(vectors[3].exc_num, 14);
+    assert_eq!(vectors[3].kind, thr::VectorKind::Fast);
+    assert_eq!(vectors[3].handler, "pend_sv_handler");
+    assert_eq!(vectors[3].priority, ::std::option::Option::None);
+    assert_eq!(vectors[4].thread, "sys_tick");
+    assert_eq!(vectors[4].kind, thr::VectorKind::Inner);
+    assert_eq!(vectors[4].priority, ::std::option::Option::None);
+    assert_eq!(vectors[5].thread, "rtc");
+    assert_eq!(vectors[5].exc_num, 19);
+    assert_eq!(vectors[5].kind, thr::VectorKind::Fast);
+    assert_eq!(vectors[5].handler, "rtc_handler");
+    assert_eq!(vectors[6].thread, "rcc");
+    assert_eq!(vectors[6].exc_num, 21);
+    assert_eq!(vectors[7].thread, "exti2");
+    assert_eq!(vectors[7].exc_num, 24);
+    assert_eq!(vectors[7].priority, ::std::option::Option::Some(0x40));
+    assert_eq!(vectors[7].guard, ::std::option::Option::Some("EXTI2_GUARD"));
+    assert_eq!(vectors[8].thread, "exti4");
+    assert_eq!(vectors[8].exc_num, 26);
+    assert_eq!(vectors[8].kind, thr::VectorKind::Inner);
+    assert_eq!(vectors[8].guard, ::std::option::Option::None);
+}